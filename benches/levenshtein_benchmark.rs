@@ -2,8 +2,38 @@ use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criteri
 use rand::distributions::{Alphanumeric, DistString};
 use rand::prelude::StdRng;
 use rand::{Rng, SeedableRng};
-use simple_search::levenshtein::base::levenshtein_similarity;
+use simple_search::levenshtein::base::{
+    levenshtein_distance_linear, levenshtein_matrix, levenshtein_similarity,
+};
 use simple_search::levenshtein::incremental::IncrementalLevenshtein;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// An allocator counting the bytes allocated, to compare the memory footprint of the implementations.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Returns the number of bytes allocated while running the function.
+fn allocated_bytes<T>(function: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    black_box(function());
+    ALLOCATED.load(Ordering::Relaxed) - before
+}
 
 fn bench_levenshtein_random_insert(c: &mut Criterion) {
     let mut group = c.benchmark_group("LevenshteinInsert");
@@ -74,9 +104,35 @@ fn bench_levenshtein_random_append(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_levenshtein_long_distance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("LevenshteinLongDistance");
+
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for len in [1000, 4000] {
+        let a = Alphanumeric.sample_string(&mut rng, len);
+        let b = Alphanumeric.sample_string(&mut rng, len);
+
+        group.bench_function(BenchmarkId::new("Matrix", len), |bencher| {
+            bencher.iter(|| black_box(levenshtein_matrix(&a, &b)[len][len]))
+        });
+        group.bench_function(BenchmarkId::new("TwoRows", len), |bencher| {
+            bencher.iter(|| black_box(levenshtein_distance_linear(&a, &b)))
+        });
+
+        println!(
+            "LevenshteinLongDistance/{len}: Matrix allocates {} bytes, TwoRows allocates {} bytes",
+            allocated_bytes(|| levenshtein_matrix(&a, &b)[len][len]),
+            allocated_bytes(|| levenshtein_distance_linear(&a, &b)),
+        );
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_levenshtein_random_insert,
-    bench_levenshtein_random_append
+    bench_levenshtein_random_append,
+    bench_levenshtein_long_distance
 );
 criterion_main!(benches);
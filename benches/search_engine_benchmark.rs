@@ -5,7 +5,7 @@ use rand::{Rng, SeedableRng};
 use simple_search::levenshtein::base::weighted_levenshtein_similarity;
 use simple_search::levenshtein::incremental::IncrementalLevenshtein;
 use simple_search::search_engine::SearchEngine;
-use std::collections::HashMap;
+use simple_search::test_utils::assert_rankings_equivalent;
 
 fn bench_erasure_overhead(c: &mut Criterion) {
     let mut group = c.benchmark_group("BenchErasureOverhead");
@@ -22,11 +22,11 @@ fn bench_erasure_overhead(c: &mut Criterion) {
 
     let regular = SearchEngine::new()
         .with_values(data.clone())
-        .with(|v, q| weighted_levenshtein_similarity(v, q));
+        .with(|v, q| weighted_levenshtein_similarity(q, v));
 
     let erased = SearchEngine::new()
         .with_values(data.clone())
-        .with(|v, q| weighted_levenshtein_similarity(v, q))
+        .with(|v, q| weighted_levenshtein_similarity(q, v))
         .erase_type();
 
     let mut query = Alphanumeric.sample_string(&mut rng, 16);
@@ -136,32 +136,18 @@ fn bench_incremental(c: &mut Criterion) {
             )
         });
 
-        let mut granular_similarities = HashMap::new();
-        regular.similarities(&query).into_iter().for_each(|(v, s)| {
-            granular_similarities.insert(v, s);
-        });
-        let mut erased_similarities = HashMap::new();
-        erased.similarities(&query).into_iter().for_each(|(v, s)| {
-            erased_similarities.insert(v, s);
-        });
-        let mut incremental_similarities = HashMap::new();
-        incremental
-            .similarities(&query)
-            .into_iter()
-            .for_each(|(v, s)| {
-                incremental_similarities.insert(v, s);
-            });
-        let mut incremental_erased_similarities = HashMap::new();
-        incremental_erased
-            .similarities(&query)
-            .into_iter()
-            .for_each(|(v, s)| {
-                incremental_erased_similarities.insert(v, s);
-            });
-
-        assert_eq!(granular_similarities, erased_similarities);
-        assert_eq!(granular_similarities, incremental_similarities);
-        assert_eq!(granular_similarities, incremental_erased_similarities);
+        let granular_similarities = regular.similarities(&query);
+        assert_rankings_equivalent(&granular_similarities, &erased.similarities(&query), 1e-9);
+        assert_rankings_equivalent(
+            &granular_similarities,
+            &incremental.similarities(&query),
+            1e-9,
+        );
+        assert_rankings_equivalent(
+            &granular_similarities,
+            &incremental_erased.similarities(&query),
+            1e-9,
+        );
     }
     group.finish();
 }
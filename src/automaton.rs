@@ -0,0 +1,131 @@
+//! This module provides the [`LevenshteinAutomaton`] struct, which is compiled once from a query
+//! and can then efficiently decide for many values, whether they are within a maximum edit distance of the query.
+
+use crate::search_engine::{Mutability, SearchEngine, SearchTrace};
+use crate::similarity::Similarity;
+
+/// A Levenshtein automaton accepting all strings within a maximum edit distance of a fixed query.
+///
+/// A state of the automaton is the column of the Levenshtein matrix for the consumed prefix of the value,
+/// with all entries larger than the maximum distance saturated to `max_distance + 1`.
+/// Matching stops as soon as no state entry is within the maximum distance anymore.
+#[derive(Debug, Clone)]
+pub struct LevenshteinAutomaton {
+    query: Vec<char>,
+    max_distance: usize,
+}
+
+impl LevenshteinAutomaton {
+    /// Constructs a new `LevenshteinAutomaton` for the given query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query the values are matched against.
+    /// * `max_distance` - The maximum edit distance accepted by the automaton.
+    pub fn new(query: &str, max_distance: usize) -> Self {
+        Self {
+            query: query.chars().collect(),
+            max_distance,
+        }
+    }
+
+    /// Returns the start state of the automaton, before any char of the value was consumed.
+    pub fn start(&self) -> Vec<usize> {
+        (0..=self.query.len())
+            .map(|i| i.min(self.max_distance + 1))
+            .collect()
+    }
+
+    /// Advances the automaton from `state` by consuming the char `c` of the value.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The current state.
+    /// * `c` - The next char of the value.
+    ///
+    /// # Returns
+    ///
+    /// Returns the state after consuming `c`.
+    pub fn step(&self, state: &[usize], c: char) -> Vec<usize> {
+        let limit = self.max_distance + 1;
+        let mut next = Vec::with_capacity(state.len());
+        next.push((state[0] + 1).min(limit));
+
+        for i in 1..state.len() {
+            let cost = if self.query[i - 1] == c { 0 } else { 1 };
+            let distance = std::cmp::min(
+                state[i] + 1,
+                std::cmp::min(next[i - 1] + 1, state[i - 1] + cost),
+            );
+            next.push(distance.min(limit));
+        }
+        next
+    }
+
+    /// Checks whether the value consumed to reach `state` is accepted by the automaton.
+    pub fn is_match(&self, state: &[usize]) -> bool {
+        state[self.query.len()] <= self.max_distance
+    }
+
+    /// Checks whether any continuation of the value consumed to reach `state` can still be accepted.
+    pub fn can_match(&self, state: &[usize]) -> bool {
+        state.iter().any(|&distance| distance <= self.max_distance)
+    }
+
+    /// Matches a value against the automaton.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to match.
+    ///
+    /// # Returns
+    ///
+    /// Returns the edit distance between the query and the value, or `None` if it exceeds the maximum distance.
+    pub fn matches(&self, value: &str) -> Option<usize> {
+        let mut state = self.start();
+        for c in value.chars() {
+            if !self.can_match(&state) {
+                return None;
+            }
+            state = self.step(&state, c);
+        }
+
+        self.is_match(&state).then(|| state[self.query.len()])
+    }
+}
+
+impl<Value, S, M: Mutability> SearchEngine<Value, str, S, M>
+where
+    Value: AsRef<str>,
+    S: Similarity<Value, str>,
+{
+    /// Performs a search for all values within the edit distance `max_distance` of the query,
+    /// using a [`LevenshteinAutomaton`] compiled once from the query. \
+    /// The configured similarity functions are not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    /// * `max_distance` - The maximum edit distance of a matching value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a matching value and the second element
+    /// is its edit distance to the query, ranked by distance with the closest values first.
+    pub fn search_with_automaton(&self, query: &str, max_distance: usize) -> Vec<(&Value, usize)> {
+        let trace = SearchTrace::start(query, self.len());
+        let automaton = LevenshteinAutomaton::new(query, max_distance);
+        let mut values: Vec<_> = self
+            .values()
+            .filter_map(|value| {
+                automaton
+                    .matches(value.as_ref())
+                    .map(|distance| (value, distance))
+            })
+            .collect();
+        values.sort_by_key(|(_, distance)| *distance);
+        // The edit distances aren't similarities, so no top score is reported.
+        trace.finish(values.len(), None);
+        values
+    }
+}
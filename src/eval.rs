@@ -0,0 +1,60 @@
+//! This module provides metrics for the quality of a ranking,
+//! which can be used to check that a change to a similarity function improves the search results.
+
+use std::collections::{HashMap, HashSet};
+
+/// Calculates the reciprocal rank of the first relevant item in a ranking. \
+/// For a single ranking this is the mean reciprocal rank, averaging over multiple queries is left to the caller.
+///
+/// # Arguments
+///
+/// * `ranked` - The items, ordered from best to worst match.
+/// * `relevant` - The items considered relevant.
+///
+/// # Returns
+///
+/// `1 / rank` of the first relevant item, where the best match has rank 1,
+/// or `0.0` if no relevant item was ranked.
+pub fn mrr(ranked: &[&str], relevant: &HashSet<&str>) -> f64 {
+    ranked
+        .iter()
+        .position(|item| relevant.contains(item))
+        .map_or(0., |index| 1. / (index + 1) as f64)
+}
+
+/// Calculates the normalized discounted cumulative gain of the first `k` items of a ranking.
+///
+/// # Arguments
+///
+/// * `ranked` - The items, ordered from best to worst match.
+/// * `relevance_scores` - The graded relevance of each item. Items without a score have a relevance of 0.
+/// * `k` - The number of ranked items to consider.
+///
+/// # Returns
+///
+/// The gain of the ranking divided by the gain of the ideal ranking, between `0.0` and `1.0`. \
+/// If no item has a positive relevance, `0.0` is returned.
+pub fn ndcg_at_k(ranked: &[&str], relevance_scores: &HashMap<&str, f64>, k: usize) -> f64 {
+    let ranked_gains = ranked
+        .iter()
+        .map(|item| relevance_scores.get(item).copied().unwrap_or(0.));
+    let dcg = discounted_cumulative_gain(ranked_gains, k);
+
+    let mut ideal_gains: Vec<f64> = relevance_scores.values().copied().collect();
+    ideal_gains.sort_by(|a, b| b.total_cmp(a));
+    let ideal_dcg = discounted_cumulative_gain(ideal_gains.into_iter(), k);
+
+    if ideal_dcg > 0. {
+        dcg / ideal_dcg
+    } else {
+        0.
+    }
+}
+
+fn discounted_cumulative_gain(gains: impl Iterator<Item = f64>, k: usize) -> f64 {
+    gains
+        .take(k)
+        .enumerate()
+        .map(|(index, gain)| gain / ((index + 2) as f64).log2())
+        .sum()
+}
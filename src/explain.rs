@@ -0,0 +1,168 @@
+//! This module breaks the similarity of every value down into the scores of the individual layers,
+//! showing which similarity function decided the ranking, or whether any of them found an exact match.
+
+use crate::search_engine::{sort_limited, Immutable, Mutable, SearchEngine, SearchTrace};
+use crate::similarity::Similarity;
+
+/// The score of a single layer: its label, the raw similarity and the similarity multiplied by the weight of the layer. \
+/// Layers added without a label have no name.
+pub type LayerScore<'a> = (Option<&'a str>, f64, f64);
+
+/// The similarity of a value, with the score of every layer, starting with layer 0.
+pub type Explanation<'a, Value> = (&'a Value, f64, Vec<LayerScore<'a>>);
+
+fn explain_value<'a, Value, Query: ?Sized, S>(
+    similarity: &'a S,
+    state: &mut S::State,
+    query_state: &S::QueryState,
+    value: &'a Value,
+    query: &Query,
+) -> Explanation<'a, Value>
+where
+    S: Similarity<Value, Query>,
+{
+    let mut scores = Vec::with_capacity(similarity.layers());
+    let total = similarity.layer_scores(state, query_state, value, query, &mut scores);
+    let layers = scores
+        .into_iter()
+        .enumerate()
+        .map(|(layer, (raw, weighted))| (similarity.label(layer), raw, weighted))
+        .collect();
+    (value, total, layers)
+}
+
+/// Scores a value and checks, whether any layer returned an exact match of at least 1.0 before weighting.
+fn score_with_exact_flag<Value, Query: ?Sized, S>(
+    similarity: &S,
+    state: &mut S::State,
+    query_state: &S::QueryState,
+    value: &Value,
+    query: &Query,
+) -> (f64, bool)
+where
+    S: Similarity<Value, Query>,
+{
+    let mut scores = Vec::with_capacity(similarity.layers());
+    let similarity = similarity.layer_scores(state, query_state, value, query, &mut scores);
+    let exact = scores.iter().any(|(raw, _)| *raw >= 1.);
+    (similarity, exact)
+}
+
+/// Sorts the scored values like the similarities of a search engine, keeping the exact match flags.
+fn sort_with_exact_flag<Value>(
+    values: Vec<((Value, bool), f64)>,
+    limit: Option<usize>,
+) -> Vec<(Value, f64, bool)> {
+    sort_limited(values, limit, true)
+        .into_iter()
+        .map(|((value, exact), similarity)| (value, similarity, exact))
+        .collect()
+}
+
+impl<Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Mutable>
+where
+    S: Similarity<Value, Query>,
+{
+    /// Calculates the similarity of every value to the query together with the scores of its layers. \
+    /// The values are returned in the order they were added. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to compare the values against.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples, each containing a value, its similarity and the scores of its layers.
+    pub fn explain(&mut self, query: &Query) -> Vec<Explanation<'_, Value>> {
+        let (similarity, values) = self.similarity_and_values_mut();
+        let query_state = similarity.query_state(query);
+        values
+            .map(|(state, value)| explain_value(similarity, state, &query_state, value, query))
+            .collect()
+    }
+
+    /// Retrieves a sorted vector of tuples like [similarities](SearchEngine::similarities),
+    /// with a flag telling whether any layer returned an exact match. \
+    /// A layer counts as an exact match, if its similarity function returned at least 1.0 before weighting,
+    /// which separates values that definitely match in some field from merely fuzzy matches. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples, each containing a value, its similarity and the exact match flag.
+    pub fn similarities_with_exact_flag(&mut self, query: &Query) -> Vec<(&Value, f64, bool)> {
+        let trace = SearchTrace::start(query, self.len());
+        let limit = self.limit();
+        let (similarity, values) = self.similarity_and_values_mut();
+        let query_state = similarity.query_state(query);
+        let values = values
+            .map(|(state, value)| {
+                let (score, exact) =
+                    score_with_exact_flag(similarity, state, &query_state, value, query);
+                ((value, exact), score)
+            })
+            .collect();
+        let values = sort_with_exact_flag(values, limit);
+        trace.finish(values.len(), values.iter().map(|(_, score, _)| *score));
+        values
+    }
+}
+
+impl<Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Immutable>
+where
+    S: Similarity<Value, Query, State = ()>,
+{
+    /// Calculates the similarity of every value to the query together with the scores of its layers. \
+    /// The values are returned in the order they were added. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to compare the values against.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples, each containing a value, its similarity and the scores of its layers.
+    pub fn explain(&self, query: &Query) -> Vec<Explanation<'_, Value>> {
+        let similarity = self.similarity();
+        let query_state = similarity.query_state(query);
+        self.values()
+            .map(|value| explain_value(similarity, &mut (), &query_state, value, query))
+            .collect()
+    }
+
+    /// Retrieves a sorted vector of tuples like [similarities](SearchEngine::similarities),
+    /// with a flag telling whether any layer returned an exact match. \
+    /// A layer counts as an exact match, if its similarity function returned at least 1.0 before weighting,
+    /// which separates values that definitely match in some field from merely fuzzy matches. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples, each containing a value, its similarity and the exact match flag.
+    pub fn similarities_with_exact_flag(&self, query: &Query) -> Vec<(&Value, f64, bool)> {
+        let trace = SearchTrace::start(query, self.len());
+        let similarity = self.similarity();
+        let query_state = similarity.query_state(query);
+        let values = self
+            .values()
+            .map(|value| {
+                let (score, exact) =
+                    score_with_exact_flag(similarity, &mut (), &query_state, value, query);
+                ((value, exact), score)
+            })
+            .collect();
+        let values = sort_with_exact_flag(values, self.limit());
+        trace.finish(values.len(), values.iter().map(|(_, score, _)| *score));
+        values
+    }
+}
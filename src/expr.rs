@@ -0,0 +1,119 @@
+//! This module provides [`Expr`], which composes similarity functions with fuzzy boolean logic. \
+//! Similarities are treated as fuzzy truth values between 0.0 and 1.0:
+//! AND takes the minimum, OR the maximum and NOT the complement `1 - x`.
+//!
+//! ```rust
+//! use simple_search::expr::Expr;
+//! use simple_search::levenshtein::base::normalized_levenshtein_similarity;
+//! use simple_search::search_engine::SearchEngine;
+//!
+//! let title = Expr::new(|book: &(&str, &str), query: &str| normalized_levenshtein_similarity(query, book.0));
+//! let author = Expr::new(|book: &(&str, &str), query: &str| normalized_levenshtein_similarity(query, book.1));
+//!
+//! let engine = SearchEngine::new()
+//!     .with_values(vec![("Ulysses", "Joyce"), ("Dubliners", "Joyce")])
+//!     .with_expr(title.or(author));
+//!
+//! let results = engine.search("Joyce");
+//! ```
+
+use std::ops::Not;
+
+use crate::search_engine::{Mutability, SearchEngine};
+use crate::similarity::{Similarity, StatelessCombination};
+
+/// A similarity function composed of other similarity functions with fuzzy boolean logic.
+pub struct Expr<'a, Value, Query: ?Sized> {
+    #[allow(clippy::type_complexity)]
+    function: Box<dyn Fn(&Value, &Query) -> f64 + 'a>,
+}
+
+impl<'a, Value: 'a, Query: ?Sized + 'a> Expr<'a, Value, Query> {
+    /// Creates a new `Expr` from a single similarity function.
+    ///
+    /// # Arguments
+    ///
+    /// * `function` - A function for determining the similarity between a value and the query,
+    ///   which should return a similarity between 0.0 and 1.0.
+    pub fn new<Func>(function: Func) -> Self
+    where
+        Func: Fn(&Value, &Query) -> f64 + 'a,
+    {
+        Self {
+            function: Box::new(function),
+        }
+    }
+
+    /// Combines two expressions, so that a value has to match both. \
+    /// The similarity is the minimum of both similarities.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The expression to combine with.
+    pub fn and(self, other: Self) -> Self {
+        Self::new(move |value, query| {
+            self.evaluate(value, query)
+                .min(other.evaluate(value, query))
+        })
+    }
+
+    /// Combines two expressions, so that a value has to match at least one of them. \
+    /// The similarity is the maximum of both similarities.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The expression to combine with.
+    pub fn or(self, other: Self) -> Self {
+        Self::new(move |value, query| {
+            self.evaluate(value, query)
+                .max(other.evaluate(value, query))
+        })
+    }
+
+    /// Calculates the similarity of the value to the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to compare.
+    /// * `query` - The query to compare the value against.
+    pub fn evaluate(&self, value: &Value, query: &Query) -> f64 {
+        (self.function)(value, query)
+    }
+}
+
+/// Negates an expression, so that a value matches if the expression doesn't. \
+/// The similarity is `1 - x`.
+impl<'a, Value: 'a, Query: ?Sized + 'a> Not for Expr<'a, Value, Query> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::new(move |value, query| 1. - self.evaluate(value, query))
+    }
+}
+
+impl<Value, Query: ?Sized, S, M: Mutability> SearchEngine<Value, Query, S, M>
+where
+    S: Similarity<Value, Query>,
+{
+    /// Adds an [`Expr`] to use for determining the similarity of a value to the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The composed similarity function.
+    #[allow(clippy::type_complexity)]
+    pub fn with_expr<'a>(
+        self,
+        expr: Expr<'a, Value, Query>,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatelessCombination<Value, Query, S, impl Fn(&Value, &Query) -> f64 + 'a>,
+        M,
+    >
+    where
+        Value: 'a,
+        Query: 'a,
+    {
+        self.with(move |value: &Value, query: &Query| expr.evaluate(value, query))
+    }
+}
@@ -0,0 +1,119 @@
+//! This module provides functions for highlighting the parts of a value matched by a query,
+//! for example to display search results in a web UI.
+
+use std::fmt::Display;
+
+use crate::levenshtein::alignment::matched_char_ranges;
+use crate::search_engine::{Immutable, Mutable, SearchEngine};
+use crate::similarity::Similarity;
+
+/// Wraps the parts of `value` matched by `query` in the given markers. \
+/// All text of the value is HTML-escaped, while the markers are inserted as they are.
+///
+/// # Arguments
+///
+/// * `value` - The value to highlight.
+/// * `query` - The query to match against the value.
+/// * `open` - The marker inserted before every matched span.
+/// * `close` - The marker inserted after every matched span.
+///
+/// # Returns
+///
+/// Returns the escaped value with all matched spans surrounded by the markers.
+pub fn highlight(value: &str, query: &str, open: &str, close: &str) -> String {
+    let ranges = matched_char_ranges(query, value);
+    let mut ranges = ranges.iter().peekable();
+
+    let mut highlighted = String::with_capacity(value.len());
+    for (index, c) in value.chars().enumerate() {
+        if ranges.peek().is_some_and(|range| range.start == index) {
+            highlighted.push_str(open);
+        }
+        escape_html_char(c, &mut highlighted);
+        if ranges.peek().is_some_and(|range| range.end == index + 1) {
+            highlighted.push_str(close);
+            ranges.next();
+        }
+    }
+    highlighted
+}
+
+fn escape_html_char(c: char, out: &mut String) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        c => out.push(c),
+    }
+}
+
+impl<Value, S> SearchEngine<Value, str, S, Mutable>
+where
+    Value: Display,
+    S: Similarity<Value, str>,
+{
+    /// Performs a search based on the given query and returns the string forms of the values ranked
+    /// by similarity, with the matched spans wrapped in the given markers. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    /// * `open` - The marker inserted before every matched span.
+    /// * `close` - The marker inserted after every matched span.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is the highlighted and HTML-escaped value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn search_highlighted(
+        &mut self,
+        query: &str,
+        open: &str,
+        close: &str,
+    ) -> Vec<(String, f64)> {
+        self.similarities(query)
+            .into_iter()
+            .map(|(value, similarity)| {
+                (
+                    highlight(&value.to_string(), query, open, close),
+                    similarity,
+                )
+            })
+            .collect()
+    }
+}
+
+impl<Value, S> SearchEngine<Value, str, S, Immutable>
+where
+    Value: Display,
+    S: Similarity<Value, str, State = ()>,
+{
+    /// Performs a search based on the given query and returns the string forms of the values ranked
+    /// by similarity, with the matched spans wrapped in the given markers. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    /// * `open` - The marker inserted before every matched span.
+    /// * `close` - The marker inserted after every matched span.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is the highlighted and HTML-escaped value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn search_highlighted(&self, query: &str, open: &str, close: &str) -> Vec<(String, f64)> {
+        self.similarities(query)
+            .into_iter()
+            .map(|(value, similarity)| {
+                (
+                    highlight(&value.to_string(), query, open, close),
+                    similarity,
+                )
+            })
+            .collect()
+    }
+}
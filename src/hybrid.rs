@@ -0,0 +1,166 @@
+//! This module provides the [`HybridEngine`] struct, which combines exact lookups by key
+//! with the fuzzy search of a [`SearchEngine`].
+
+use std::borrow::Borrow;
+use std::ops::Range;
+
+use crate::search_engine::{Immutable, Mutability, Mutable, SearchEngine};
+use crate::similarity::Similarity;
+
+/// A search engine maintaining a sorted index of exact keys alongside a fuzzy [`SearchEngine`].
+///
+/// The key of every value is derived by a key function.
+/// Exact lookups by key take `O(log n)`, while [search](HybridEngine::search) ranks all values by similarity.
+pub struct HybridEngine<Key, Value, Query: ?Sized, S, M: Mutability, KeyFunc>
+where
+    Key: Ord,
+    KeyFunc: Fn(&Value) -> Key,
+    S: Similarity<Value, Query>,
+{
+    engine: SearchEngine<Value, Query, S, M>,
+    key_func: KeyFunc,
+    index: Vec<(Key, usize)>,
+}
+
+impl<Key, Value, Query: ?Sized, S, M: Mutability, KeyFunc>
+    HybridEngine<Key, Value, Query, S, M, KeyFunc>
+where
+    Key: Ord,
+    KeyFunc: Fn(&Value) -> Key,
+    S: Similarity<Value, Query>,
+{
+    /// Creates a new `HybridEngine`, indexing all values of the given search engine by their key.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The search engine used for fuzzy searches.
+    /// * `key_func` - A function deriving the exact key of a value.
+    pub fn new(engine: SearchEngine<Value, Query, S, M>, key_func: KeyFunc) -> Self {
+        let mut index: Vec<_> = engine
+            .values()
+            .enumerate()
+            .map(|(i, value)| (key_func(value), i))
+            .collect();
+        index.sort();
+        Self {
+            engine,
+            key_func,
+            index,
+        }
+    }
+
+    /// Adds a single value to the search engine and the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to be added.
+    pub fn add_value(&mut self, value: Value) {
+        let entry = ((self.key_func)(&value), self.engine.values().len());
+        let position = self.index.partition_point(|e| *e < entry);
+        self.index.insert(position, entry);
+        self.engine.add_value(value);
+    }
+
+    /// Returns the underlying search engine.
+    pub fn engine(&self) -> &SearchEngine<Value, Query, S, M> {
+        &self.engine
+    }
+
+    fn exact_range<K>(&self, key: &K) -> Range<usize>
+    where
+        Key: Borrow<K>,
+        K: Ord + ?Sized,
+    {
+        let start = self.index.partition_point(|(k, _)| k.borrow() < key);
+        let end = self.index.partition_point(|(k, _)| k.borrow() <= key);
+        start..end
+    }
+
+    /// Looks up a value by its exact key in `O(log n)`.
+    /// If multiple values share the key, the one added first is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    ///
+    /// # Returns
+    ///
+    /// Returns a reference to the value with the given key, or `None` if there is none.
+    pub fn lookup_exact<K>(&self, key: &K) -> Option<&Value>
+    where
+        Key: Borrow<K>,
+        K: Ord + ?Sized,
+    {
+        let range = self.exact_range(key);
+        self.index
+            .get(range.start)
+            .filter(|_| !range.is_empty())
+            .and_then(|(_, i)| self.engine.get(*i))
+    }
+
+    /// Returns all values with the exact key, in insertion order.
+    fn exact_values(&self, range: Range<usize>) -> Vec<&Value> {
+        self.index[range]
+            .iter()
+            .filter_map(|(_, i)| self.engine.get(*i))
+            .collect()
+    }
+}
+
+impl<Key, Value, Query: ?Sized, S, KeyFunc> HybridEngine<Key, Value, Query, S, Mutable, KeyFunc>
+where
+    Key: Ord,
+    KeyFunc: Fn(&Value) -> Key,
+    S: Similarity<Value, Query>,
+{
+    /// Performs a fuzzy search based on the given query and returns a vector of references to the values ranked
+    /// by similarity, with the most similar value first. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    pub fn search(&mut self, query: &Query) -> Vec<&Value> {
+        self.engine.search(query)
+    }
+
+    /// Returns all values whose key exactly equals the query, or falls back to a fuzzy search, if there are none. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    pub fn search_exact_or_fuzzy(&mut self, query: &Query) -> Vec<&Value>
+    where
+        Key: Borrow<Query>,
+        Query: Ord,
+    {
+        let range = self.exact_range(query);
+        if range.is_empty() {
+            self.engine.search(query)
+        } else {
+            self.exact_values(range)
+        }
+    }
+}
+
+impl<Key, Value, Query: ?Sized, S, KeyFunc> HybridEngine<Key, Value, Query, S, Immutable, KeyFunc>
+where
+    Key: Ord,
+    KeyFunc: Fn(&Value) -> Key,
+    S: Similarity<Value, Query, State = ()>,
+{
+    /// Performs a fuzzy search based on the given query and returns a vector of references to the values ranked
+    /// by similarity, with the most similar value first. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    pub fn search(&self, query: &Query) -> Vec<&Value> {
+        self.engine.search(query)
+    }
+
+    /// Returns all values whose key exactly equals the query, or falls back to a fuzzy search, if there are none. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    pub fn search_exact_or_fuzzy(&self, query: &Query) -> Vec<&Value>
+    where
+        Key: Borrow<Query>,
+        Query: Ord,
+    {
+        let range = self.exact_range(query);
+        if range.is_empty() {
+            self.engine.search(query)
+        } else {
+            self.exact_values(range)
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! This module provides an inverse document frequency boost for the [`SearchEngine`],
+//! ranking values sharing rare tokens with the query above values sharing only common ones.
+
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use crate::search_engine::{CombineMode, Mutability, SearchEngine};
+use crate::similarity::Similarity;
+
+/// The inverse document frequencies of the tokens of a corpus.
+#[derive(Clone)]
+struct InverseDocumentFrequencies {
+    frequencies: HashMap<String, usize>,
+    documents: usize,
+}
+
+impl InverseDocumentFrequencies {
+    fn new(documents: impl Iterator<Item = HashSet<String>>) -> Self {
+        let mut frequencies = HashMap::new();
+        let mut count = 0;
+        for tokens in documents {
+            count += 1;
+            for token in tokens {
+                *frequencies.entry(token).or_insert(0) += 1;
+            }
+        }
+        Self {
+            frequencies,
+            documents: count,
+        }
+    }
+
+    /// The smoothed inverse document frequency, which is at least 1.0 and highest for unknown tokens.
+    fn idf(&self, token: &str) -> f64 {
+        let frequency = self.frequencies.get(token).copied().unwrap_or(0);
+        ((self.documents + 1) as f64 / (frequency + 1) as f64).ln() + 1.
+    }
+}
+
+/// A layer scoring the rarity of the query tokens contained in a value,
+/// as added by [with_idf_boost](SearchEngine::with_idf_boost).
+pub struct IdfBoost<Value, Query: ?Sized, Inner, Tokenizer>
+where
+    Tokenizer: Fn(&str) -> Vec<String>,
+    Inner: Similarity<Value, Query>,
+{
+    weight: f64,
+    tokenizer: Tokenizer,
+    /// The document frequencies of all values, recomputed whenever the values change.
+    frequencies: InverseDocumentFrequencies,
+    inner: Inner,
+    phantom: PhantomData<(Value, Query)>,
+}
+
+impl<Value, Query: ?Sized, Inner, Tokenizer> IdfBoost<Value, Query, Inner, Tokenizer>
+where
+    Tokenizer: Fn(&str) -> Vec<String>,
+    Inner: Similarity<Value, Query>,
+{
+    fn tokens(&self, s: &str) -> HashSet<String> {
+        (self.tokenizer)(s).into_iter().collect()
+    }
+
+    /// The share of the inverse document frequencies of the query tokens, that are found in the value.
+    fn boost(&self, value: &str, query: &str) -> f64 {
+        let value_tokens = self.tokens(value);
+        let (matched, total) =
+            self.tokens(query)
+                .iter()
+                .fold((0., 0.), |(matched, total), token| {
+                    let idf = self.frequencies.idf(token);
+                    if value_tokens.contains(token) {
+                        (matched + idf, total + idf)
+                    } else {
+                        (matched, total + idf)
+                    }
+                });
+        if total == 0. {
+            0.
+        } else {
+            matched / total
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, Inner, Tokenizer> Similarity<Value, Query>
+    for IdfBoost<Value, Query, Inner, Tokenizer>
+where
+    Value: AsRef<str>,
+    Query: AsRef<str>,
+    Tokenizer: Fn(&str) -> Vec<String>,
+    Inner: Similarity<Value, Query>,
+{
+    type State = Inner::State;
+    type QueryState = Inner::QueryState;
+
+    fn state(&self, value: &Value) -> Self::State {
+        self.inner.state(value)
+    }
+
+    fn query_state(&self, query: &Query) -> Self::QueryState {
+        self.inner.query_state(query)
+    }
+
+    fn similarity(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+    ) -> f64 {
+        let similarity = self.boost(value.as_ref(), query.as_ref()) * self.weight;
+        let inner_similarity = self.inner.similarity(state, query_state, value, query);
+
+        CombineMode::Max.combine(inner_similarity, similarity, self.inner.layers())
+    }
+
+    fn layer_scores(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        scores: &mut Vec<(f64, f64)>,
+    ) -> f64 {
+        let inner_similarity = self
+            .inner
+            .layer_scores(state, query_state, value, query, scores);
+        let similarity = self.boost(value.as_ref(), query.as_ref());
+        scores.push((similarity, similarity * self.weight));
+
+        CombineMode::Max.combine(
+            inner_similarity,
+            similarity * self.weight,
+            self.inner.layers(),
+        )
+    }
+
+    fn refresh(&mut self, values: &[&Value]) {
+        self.inner.refresh(values);
+        self.frequencies =
+            InverseDocumentFrequencies::new(values.iter().map(|v| self.tokens(v.as_ref())));
+    }
+
+    fn uses_corpus(&self) -> bool {
+        true
+    }
+
+    fn layers(&self) -> usize {
+        self.inner.layers() + 1
+    }
+
+    fn weight_mut(&mut self, layer: usize) -> Option<&mut f64> {
+        if layer == self.inner.layers() {
+            Some(&mut self.weight)
+        } else {
+            self.inner.weight_mut(layer)
+        }
+    }
+
+    fn label(&self, layer: usize) -> Option<&str> {
+        if layer == self.inner.layers() {
+            None
+        } else {
+            self.inner.label(layer)
+        }
+    }
+
+    fn combine_mode(&self, layer: usize) -> CombineMode {
+        if layer == self.inner.layers() {
+            CombineMode::Max
+        } else {
+            self.inner.combine_mode(layer)
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, Inner, Tokenizer> Clone for IdfBoost<Value, Query, Inner, Tokenizer>
+where
+    Tokenizer: Fn(&str) -> Vec<String> + Clone,
+    Inner: Similarity<Value, Query> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            weight: self.weight,
+            tokenizer: self.tokenizer.clone(),
+            frequencies: self.frequencies.clone(),
+            inner: self.inner.clone(),
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, S, M: Mutability> SearchEngine<Value, Query, S, M>
+where
+    S: Similarity<Value, Query>,
+{
+    /// Adds a function scoring the rarity of the query tokens contained in a value. \
+    /// The score is the sum of the inverse document frequencies of the query tokens found in the value,
+    /// divided by the sum of the inverse document frequencies of all query tokens.
+    /// So a value sharing a rare token with the query scores higher than one sharing only a common token.
+    ///
+    /// The document frequencies are computed from the values present when this function is called,
+    /// and recomputed from all values whenever values are added or removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokenizer` - A function splitting a value or the query into tokens.
+    pub fn with_idf_boost<Tokenizer>(
+        self,
+        tokenizer: Tokenizer,
+    ) -> SearchEngine<Value, Query, IdfBoost<Value, Query, S, Tokenizer>, M>
+    where
+        Value: AsRef<str>,
+        Query: AsRef<str>,
+        Tokenizer: Fn(&str) -> Vec<String>,
+    {
+        self.with_layer(|similarity| IdfBoost {
+            weight: 1.,
+            tokenizer,
+            frequencies: InverseDocumentFrequencies::new(std::iter::empty()),
+            inner: similarity,
+            phantom: Default::default(),
+        })
+    }
+}
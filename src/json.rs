@@ -0,0 +1,108 @@
+//! This module provides functions for searching [`serde_json::Value`] records,
+//! which compare the query to the text of all string fields of a record.
+//!
+//! It is only available with the `serde_json` feature.
+
+use serde_json::Value;
+
+use crate::levenshtein::partial::partial_ratio;
+use crate::search_engine::{Mutability, SearchEngine};
+use crate::similarity::{Similarity, StatelessCombination};
+
+/// Concatenates all string leaves of a JSON value, separated by spaces. \
+/// Nested objects and arrays are visited in order, while numbers, booleans, null and the keys of objects are skipped.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to extract the text from.
+///
+/// # Returns
+///
+/// Returns the text of all string leaves.
+pub fn json_text(value: &Value) -> String {
+    let mut text = String::new();
+    push_strings(value, &mut text);
+    text
+}
+
+/// Concatenates the string leaves below the given fields of a JSON value, like [`json_text`]. \
+/// The fields are addressed with [JSON pointers](https://datatracker.ietf.org/doc/html/rfc6901), e.g. `"/author/name"`,
+/// and fields that don't exist are skipped.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to extract the text from.
+/// * `paths` - The JSON pointers of the fields to search.
+///
+/// # Returns
+///
+/// Returns the text of all string leaves below the given fields.
+pub fn json_text_at<P: AsRef<str>>(value: &Value, paths: &[P]) -> String {
+    let mut text = String::new();
+    for field in paths.iter().filter_map(|path| value.pointer(path.as_ref())) {
+        push_strings(field, &mut text);
+    }
+    text
+}
+
+fn push_strings(value: &Value, text: &mut String) {
+    match value {
+        Value::String(s) => {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(s);
+        }
+        Value::Array(values) => values.iter().for_each(|value| push_strings(value, text)),
+        Value::Object(fields) => fields.values().for_each(|value| push_strings(value, text)),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+impl<Query: ?Sized, S, M: Mutability> SearchEngine<Value, Query, S, M>
+where
+    S: Similarity<Value, Query>,
+{
+    /// Adds a function comparing the query to the [text](json_text) of all string fields of a JSON value. \
+    /// As the text of a record is usually much longer than the query, the [partial_ratio] is used,
+    /// which scores the best matching part of the text.
+    #[allow(clippy::type_complexity)]
+    pub fn with_json(
+        self,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatelessCombination<Value, Query, S, impl Fn(&Value, &Query) -> f64>,
+        M,
+    >
+    where
+        Query: AsRef<str>,
+    {
+        self.with(|value: &Value, query: &Query| partial_ratio(&json_text(value), query.as_ref()))
+    }
+
+    /// Adds a function comparing the query to the [text](json_text_at) of the given fields of a JSON value,
+    /// like [with_json](SearchEngine::with_json).
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The JSON pointers of the fields to search, e.g. `"/author/name"`.
+    #[allow(clippy::type_complexity)]
+    pub fn with_json_fields(
+        self,
+        paths: &[&str],
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatelessCombination<Value, Query, S, impl Fn(&Value, &Query) -> f64>,
+        M,
+    >
+    where
+        Query: AsRef<str>,
+    {
+        let paths: Vec<String> = paths.iter().map(|path| path.to_string()).collect();
+        self.with(move |value: &Value, query: &Query| {
+            partial_ratio(&json_text_at(value, &paths), query.as_ref())
+        })
+    }
+}
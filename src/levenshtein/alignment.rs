@@ -0,0 +1,167 @@
+//! This module defines functions for aligning a query against a value,
+//! to determine which parts of the value were matched by the query.
+
+use std::ops::Range;
+
+use crate::levenshtein::base::levenshtein_matrix_generic;
+
+/// The kind of edit a [`Span`] of an alignment stands for, when turning the query into the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    /// The chars are equal in the query and the value.
+    Match,
+    /// The chars only exist in the value, so the query range is empty.
+    Insert,
+    /// The chars only exist in the query, so the value range is empty.
+    Delete,
+    /// The chars of the query are replaced by the same number of different chars in the value.
+    Substitute,
+}
+
+/// A run of equal edits of an alignment, with the char ranges it covers in the query and the value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// The kind of edit shared by all chars of the span.
+    pub kind: SpanKind,
+    /// The char index range into the query.
+    pub query: Range<usize>,
+    /// The char index range into the value.
+    pub value: Range<usize>,
+}
+
+/// Aligns the query against the whole value with the minimal number of Levenshtein edits,
+/// e.g. to highlight the parts of a search result that match the query.
+///
+/// # Arguments
+///
+/// * `query` - The query to align.
+/// * `value` - The value to align the query against.
+///
+/// # Returns
+///
+/// Returns the spans of the alignment in order, where consecutive edits of the same kind are merged.
+/// Together they cover every char of both strings exactly once.
+pub fn align(query: &str, value: &str) -> Vec<Span> {
+    let q: Vec<char> = query.chars().collect();
+    let v: Vec<char> = value.chars().collect();
+    let matrix = levenshtein_matrix_generic(&q, &v);
+
+    let mut kinds = Vec::new();
+    let (mut i, mut j) = (q.len(), v.len());
+    while i > 0 || j > 0 {
+        let kind = if i > 0 && j > 0 && q[i - 1] == v[j - 1] && matrix[i][j] == matrix[i - 1][j - 1]
+        {
+            SpanKind::Match
+        } else if i > 0 && j > 0 && matrix[i][j] == matrix[i - 1][j - 1] + 1 {
+            SpanKind::Substitute
+        } else if i > 0 && matrix[i][j] == matrix[i - 1][j] + 1 {
+            SpanKind::Delete
+        } else {
+            SpanKind::Insert
+        };
+
+        if kind != SpanKind::Insert {
+            i -= 1;
+        }
+        if kind != SpanKind::Delete {
+            j -= 1;
+        }
+        kinds.push(kind);
+    }
+    kinds.reverse();
+
+    let mut spans: Vec<Span> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for kind in kinds {
+        let (next_i, next_j) = match kind {
+            SpanKind::Insert => (i, j + 1),
+            SpanKind::Delete => (i + 1, j),
+            SpanKind::Match | SpanKind::Substitute => (i + 1, j + 1),
+        };
+        match spans.last_mut() {
+            Some(span) if span.kind == kind => {
+                span.query.end = next_i;
+                span.value.end = next_j;
+            }
+            _ => spans.push(Span {
+                kind,
+                query: i..next_i,
+                value: j..next_j,
+            }),
+        }
+        (i, j) = (next_i, next_j);
+    }
+    spans
+}
+
+/// Computes the ranges of chars in `value` that are matched exactly by chars of `query`.
+///
+/// The query is aligned against the best matching substring of the value,
+/// so leading and trailing chars of the value do not count as edits.
+///
+/// # Arguments
+///
+/// * `query` - The query to align.
+/// * `value` - The value to align the query against.
+///
+/// # Returns
+///
+/// Returns a vector of non-overlapping, ascending char index ranges into `value`.
+pub(crate) fn matched_char_ranges(query: &str, value: &str) -> Vec<Range<usize>> {
+    let q: Vec<char> = query.chars().collect();
+    let v: Vec<char> = value.chars().collect();
+
+    if q.is_empty() || v.is_empty() {
+        return Vec::new();
+    }
+
+    // Leading chars of the value can be skipped for free.
+    let mut matrix = vec![vec![0; v.len() + 1]; q.len() + 1];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for i in 1..=q.len() {
+        for j in 1..=v.len() {
+            let cost = if q[i - 1] == v[j - 1] { 0 } else { 1 };
+
+            matrix[i][j] = std::cmp::min(
+                matrix[i - 1][j] + 1,
+                std::cmp::min(matrix[i][j - 1] + 1, matrix[i - 1][j - 1] + cost),
+            );
+        }
+    }
+
+    // Trailing chars of the value can be skipped for free.
+    let mut j = (0..=v.len())
+        .rev()
+        .min_by_key(|&j| matrix[q.len()][j])
+        .unwrap_or(0);
+    let mut i = q.len();
+
+    let mut matched = Vec::new();
+    while i > 0 && j > 0 {
+        if q[i - 1] == v[j - 1] && matrix[i][j] == matrix[i - 1][j - 1] {
+            matched.push(j - 1);
+            i -= 1;
+            j -= 1;
+        } else if matrix[i][j] == matrix[i][j - 1] + 1 {
+            j -= 1;
+        } else if matrix[i][j] == matrix[i - 1][j] + 1 {
+            i -= 1;
+        } else {
+            i -= 1;
+            j -= 1;
+        }
+    }
+    matched.reverse();
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for index in matched {
+        match ranges.last_mut() {
+            Some(range) if range.end == index => range.end += 1,
+            _ => ranges.push(index..index + 1),
+        }
+    }
+    ranges
+}
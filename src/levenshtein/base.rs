@@ -2,6 +2,7 @@
 //! and similarity between two strings
 
 use std::char;
+use std::collections::HashMap;
 
 /// Computes the Levenshtein distance between two strings.
 ///
@@ -14,10 +15,142 @@ use std::char;
 ///
 /// Returns the Levenshtein distance as a `usize`.
 pub fn levenshtein_distance(a: &str, b: &str) -> usize {
-    let len_a = a.len();
-    let len_b = b.len();
-    let matrix = levenshtein_matrix(a, b);
-    matrix[len_a][len_b]
+    // Shared ends never need an edit, so only the differing middle is compared.
+    let (a, b) = trim_common_affixes(a, b);
+    levenshtein_distance_linear(a, b)
+}
+
+/// Removes the common prefix and the common suffix of two strings,
+/// where the suffix is only searched after the prefix, so both never overlap.
+fn trim_common_affixes<'a, 'b>(a: &'a str, b: &'b str) -> (&'a str, &'b str) {
+    let prefix = common_prefix(a, b);
+    let a = &a[char_offset(a, prefix)..];
+    let b = &b[char_offset(b, prefix)..];
+
+    let suffix = common_suffix(a, b);
+    let a = &a[..a.len() - suffix_bytes(a, suffix)];
+    let b = &b[..b.len() - suffix_bytes(b, suffix)];
+    (a, b)
+}
+
+/// The byte offset of the char with the given index, or the length of the string if it has fewer chars.
+fn char_offset(s: &str, chars: usize) -> usize {
+    s.char_indices()
+        .nth(chars)
+        .map_or(s.len(), |(offset, _)| offset)
+}
+
+/// The number of bytes of the last `chars` chars of a string.
+fn suffix_bytes(s: &str, chars: usize) -> usize {
+    s.chars().rev().take(chars).map(char::len_utf8).sum()
+}
+
+/// Computes the Levenshtein distance between the characters of two strings,
+/// keeping only two rows of the matrix instead of the whole [levenshtein_matrix]. \
+/// The rows are as long as the shorter string, so the memory is `O(min(len_a, len_b))`.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns the Levenshtein distance as a `usize`.
+pub fn levenshtein_distance_linear(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    linear_distance_generic(&a, &b)
+}
+
+/// Computes the Levenshtein distance between two slices with two rows, iterating over the longer slice.
+fn linear_distance_generic<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    // The distance is symmetric, so the rows can always span the shorter slice.
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            current[j] = std::cmp::min(
+                previous[j] + 1,
+                std::cmp::min(current[j - 1] + 1, previous[j - 1] + cost),
+            );
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Computes the Levenshtein distance between the characters of two strings,
+/// if it doesn't exceed `max`.
+///
+/// Only the diagonal band of width `2 * max + 1` of the matrix is filled, as cells further away
+/// from the diagonal are always larger than `max`. This makes the cost `O(len * max)` instead of `O(len_a * len_b)`.
+/// The computation stops as soon as the distance provably exceeds `max`.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+/// * `max` - The maximum distance of interest.
+///
+/// # Returns
+///
+/// Returns the Levenshtein distance, or `None` if it is larger than `max`.
+pub fn bounded_levenshtein_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    // The distance never exceeds the longer length, so a larger bound doesn't widen the band.
+    let max = max.min(a.len().max(b.len()));
+    // Any value above max is stored as this, as only distances within the bound matter.
+    let exceeded = max + 1;
+
+    let mut previous: Vec<usize> = (0..=b.len()).map(|j| j.min(exceeded)).collect();
+    let mut current = vec![exceeded; b.len() + 1];
+
+    for i in 1..=a.len() {
+        let start = i.saturating_sub(max).max(1);
+        let end = (i + max).min(b.len());
+
+        current[start - 1] = if start == 1 {
+            i.min(exceeded)
+        } else {
+            exceeded
+        };
+        let mut row_min = current[start - 1];
+        for j in start..=end {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            current[j] = std::cmp::min(
+                previous[j] + 1,
+                std::cmp::min(current[j - 1] + 1, previous[j - 1] + cost),
+            )
+            .min(exceeded);
+            row_min = row_min.min(current[j]);
+        }
+        // The band of the next row reaches one cell further.
+        if end < b.len() {
+            current[end + 1] = exceeded;
+        }
+
+        // The distance can never drop below the minimum of a row.
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    Some(previous[b.len()]).filter(|&distance| distance <= max)
 }
 
 /// Computes the similarity ratio based on the Levenshtein distance between two strings.
@@ -31,8 +164,12 @@ pub fn levenshtein_distance(a: &str, b: &str) -> usize {
 ///
 /// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
 pub fn levenshtein_similarity(a: &str, b: &str) -> f64 {
+    // Identical strings don't need a matrix, two empty strings keep their similarity of 0.0.
+    if a == b && !a.is_empty() {
+        return 1.;
+    }
     let distance = levenshtein_distance(a, b);
-    let max_distance = a.len().max(b.len());
+    let max_distance = a.chars().count().max(b.chars().count());
     if max_distance == 0 {
         0.
     } else {
@@ -40,12 +177,398 @@ pub fn levenshtein_similarity(a: &str, b: &str) -> f64 {
     }
 }
 
+/// Computes a symmetric similarity ratio based on the Levenshtein distance between the characters of two strings.
+///
+/// The similarity is defined as `1 - distance / max(len_a, len_b)`, where the lengths are counted in chars.
+/// Unlike [`weighted_levenshtein_similarity`], swapping the arguments never changes the result,
+/// which makes this the recommended default metric. Two empty strings are considered identical.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn normalized_levenshtein_similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.;
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_distance = a.len().max(b.len());
+    if max_distance == 0 {
+        return 1.;
+    }
+    let distance = linear_distance_generic(&a, &b);
+    1. - distance as f64 / max_distance as f64
+}
+
+/// Computes the Damerau-Levenshtein distance between the characters of two strings,
+/// which counts swapping two adjacent characters, like "teh" and "the", as a single edit. \
+/// Characters may still be edited between and after being transposed, so e.g. "ca" and "abc" have a distance of 2,
+/// not of 3 like with the restricted optimal string alignment distance.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns the Damerau-Levenshtein distance as a `usize`.
+pub fn damerau_levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let len_a = a.len();
+    let len_b = b.len();
+
+    // The matrix has an additional leading row and column with a distance exceeding any real one,
+    // so transpositions reaching before the start of a string are never chosen.
+    let infinity = len_a + len_b;
+    let mut matrix = vec![vec![0; len_b + 2]; len_a + 2];
+    matrix[0][0] = infinity;
+    for i in 0..=len_a {
+        matrix[i + 1][0] = infinity;
+        matrix[i + 1][1] = i;
+    }
+    for j in 0..=len_b {
+        matrix[0][j + 1] = infinity;
+        matrix[1][j + 1] = j;
+    }
+
+    // The last row of `a`, in which a character was seen.
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+    for i in 1..=len_a {
+        // The last column of `b` in this row, whose character matched.
+        let mut last_match_column = 0;
+        for j in 1..=len_b {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_match_column;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_match_column = j;
+                0
+            } else {
+                1
+            };
+
+            matrix[i + 1][j + 1] = (matrix[i][j] + cost)
+                .min(matrix[i + 1][j] + 1)
+                .min(matrix[i][j + 1] + 1)
+                .min(matrix[k][l] + (i - k - 1) + 1 + (j - l - 1));
+        }
+        last_row.insert(a[i - 1], i);
+    }
+
+    matrix[len_a + 1][len_b + 1]
+}
+
+/// Computes the similarity ratio based on the [Damerau-Levenshtein distance](damerau_levenshtein_distance)
+/// between the characters of two strings.
+///
+/// The similarity is defined as `1 - distance / max(len_a, len_b)` like for [`normalized_levenshtein_similarity`],
+/// so a transposed pair of characters costs as much as a single substitution. Two empty strings are considered identical.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn damerau_levenshtein_similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.;
+    }
+    let max_distance = a.chars().count().max(b.chars().count());
+    let distance = damerau_levenshtein_distance(a, b);
+    1. - distance as f64 / max_distance as f64
+}
+
+/// Computes the Hamming distance between the characters of two strings of equal length,
+/// which is the number of positions with different characters. Only substitutions are considered,
+/// which suits fixed-length codes like SKUs.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns the Hamming distance, or `None` if the strings differ in their number of chars.
+pub fn hamming_distance(a: &str, b: &str) -> Option<usize> {
+    if a.chars().count() != b.chars().count() {
+        return None;
+    }
+    Some(a.chars().zip(b.chars()).filter(|(c1, c2)| c1 != c2).count())
+}
+
+/// Computes the similarity ratio based on the [Hamming distance](hamming_distance) between two strings,
+/// defined as `1 - distance / len`. \
+/// Strings of different lengths have a similarity of 0.0, while two empty strings are considered identical.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn hamming_similarity(a: &str, b: &str) -> f64 {
+    match hamming_distance(a, b) {
+        None => 0.,
+        Some(0) => 1.,
+        Some(distance) => 1. - distance as f64 / a.chars().count() as f64,
+    }
+}
+
+/// Determines how the Levenshtein distance is normalized into a similarity ratio
+/// by [`levenshtein_similarity_normalized`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormBy {
+    /// Normalize by the length of the longer string, identical to [`normalized_levenshtein_similarity`].
+    Max,
+    /// Normalize by the length of the query only, comparing it against the best matching substring of the value.
+    /// This is useful for autocompletion, where a short query shouldn't be penalized for long values,
+    /// and may match any word of the value, not only the first one.
+    Query,
+}
+
+/// Computes a similarity ratio based on the Levenshtein distance between the characters of two strings,
+/// normalized as specified by `norm`.
+///
+/// With [`NormBy::Query`] the similarity is `(len_query - distance) / len_query`,
+/// where `distance` is the smallest edit distance between the query and any substring of the value,
+/// so `"gat"` matches the `"Gat"` of `"The Great Gatsby"` with a single edit.
+/// An empty query matches every value perfectly.
+///
+/// # Arguments
+///
+/// * `a` - The query string.
+/// * `b` - The value string to compare against.
+/// * `norm` - The normalization to use.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn levenshtein_similarity_normalized(a: &str, b: &str, norm: NormBy) -> f64 {
+    match norm {
+        NormBy::Max => normalized_levenshtein_similarity(a, b),
+        NormBy::Query => {
+            let a: Vec<char> = a.chars().collect();
+            let b: Vec<char> = b.chars().collect();
+            if a.is_empty() {
+                return 1.;
+            }
+            // The first row is all zeros, so the substring may start anywhere in the value for free,
+            // and it may end anywhere, as the minimum of the last row is taken.
+            let mut row = vec![0; b.len() + 1];
+            for (i, ca) in a.iter().enumerate() {
+                let mut diagonal = row[0];
+                row[0] = i + 1;
+                for (j, cb) in b.iter().enumerate() {
+                    let cost = if ca == cb { 0 } else { 1 };
+                    let substitution = diagonal + cost;
+                    diagonal = row[j + 1];
+                    row[j + 1] = substitution.min(row[j + 1] + 1).min(row[j] + 1);
+                }
+            }
+            let distance = row.into_iter().min().unwrap_or(a.len());
+            (a.len() - distance) as f64 / a.len() as f64
+        }
+    }
+}
+
+/// Computes a similarity ratio based on a Levenshtein distance, where the cost of every edit decays with its position,
+/// so edits at the start of the strings weigh more than edits at the end.
+///
+/// An edit of the char at position `p` costs `decay^p`. For substitutions and deletions the position in `a` is used,
+/// for insertions the position in `b`.
+/// The distance is normalized by the cost of substituting or inserting every char of the longer string.
+/// A decay of 1.0 results in the same similarity as [`normalized_levenshtein_similarity`].
+///
+/// # Arguments
+///
+/// * `a` - The query string.
+/// * `b` - The value string to compare against.
+/// * `decay` - The factor by which the cost of an edit decreases per position, usually within `(0.0, 1.0]`.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn positional_levenshtein_similarity(a: &str, b: &str, decay: f64) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let len_a = a.len();
+    let len_b = b.len();
+
+    let max_len = len_a.max(len_b);
+    if max_len == 0 {
+        return 1.;
+    }
+    let costs: Vec<f64> = (0..max_len).map(|p| decay.powi(p as i32)).collect();
+
+    let mut matrix = vec![vec![0.; len_b + 1]; len_a + 1];
+    for i in 1..=len_a {
+        matrix[i][0] = matrix[i - 1][0] + costs[i - 1];
+    }
+    for j in 1..=len_b {
+        matrix[0][j] = matrix[0][j - 1] + costs[j - 1];
+    }
+
+    for i in 1..=len_a {
+        for j in 1..=len_b {
+            let substitution = if a[i - 1] == b[j - 1] {
+                0.
+            } else {
+                costs[i - 1]
+            };
+
+            matrix[i][j] = f64::min(
+                matrix[i - 1][j] + costs[i - 1],
+                f64::min(
+                    matrix[i][j - 1] + costs[j - 1],
+                    matrix[i - 1][j - 1] + substitution,
+                ),
+            );
+        }
+    }
+
+    let max_distance: f64 = costs.iter().sum();
+    1. - matrix[len_a][len_b] / max_distance
+}
+
+/// Computes a weighted similarity ratio, which considers the length and type of edit
+/// operations required to convert the first string into the second.
+///
+/// This metric is not symmetric, the query should be passed as the first argument and the value as the second.
+/// This is the same orientation used by
+/// [`IncrementalLevenshtein::weighted_similarity`](crate::levenshtein::incremental::IncrementalLevenshtein::weighted_similarity).
+///
+/// # Arguments
+///
+/// * `a` - The query string.
+/// * `b` - The value string to compare against.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the weighted similarity ratio.
 pub fn weighted_levenshtein_similarity(a: &str, b: &str) -> f64 {
+    // Identical strings don't need a matrix, two empty strings keep their similarity of 0.0.
+    if a == b && !a.is_empty() {
+        return 1.;
+    }
+    weighted_levenshtein_similarity_with(a, b, false)
+}
+
+/// Computes a weighted similarity ratio like [`weighted_levenshtein_similarity`],
+/// with control over the bonus for runs of unchanged chars. \
+/// With the bonus, an unchanged run of length `n` reduces the distance by `ln(1 + n)`,
+/// without it unchanged chars don't contribute to the distance at all,
+/// which is what [`weighted_levenshtein_similarity`] does. \
+/// The bonus favours values sharing long runs with the query, so similarities can exceed 1.0.
+///
+/// # Arguments
+///
+/// * `a` - The query string.
+/// * `b` - The value string to compare against.
+/// * `match_bonus` - Whether unchanged runs reduce the distance.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the weighted similarity ratio.
+pub fn weighted_levenshtein_similarity_with(a: &str, b: &str, match_bonus: bool) -> f64 {
+    if a == b && !a.is_empty() {
+        return 1.;
+    }
+    if match_bonus {
+        // The shared ends are unchanged runs earning a bonus, so the full matrix is needed.
+        let matrix = levenshtein_matrix(a, b);
+        return weighted_edit_similarity(&matrix, a, b, true);
+    }
+    // Only the differing middle needs a matrix, the shared ends are free of cost.
+    let (middle_a, middle_b) = trim_common_affixes(a, b);
+    let matrix = levenshtein_matrix(middle_a, middle_b);
+    let distance = weighted_edit_distance(&matrix, middle_a, middle_b, match_bonus);
+    weighted_ratio(distance, a, b)
+}
+
+/// Computes the weighted similarity ratio of two strings, ignoring all whitespace. \
+/// Differences in spacing, such as `"TheGreatGatsby"` and `"The Great Gatsby"`, therefore don't count as edits.
+///
+/// Like [`weighted_levenshtein_similarity`], this metric is not symmetric.
+///
+/// # Arguments
+///
+/// * `a` - The query string.
+/// * `b` - The value string to compare against.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the weighted similarity ratio of the strings without whitespace.
+pub fn whitespace_tolerant_similarity(a: &str, b: &str) -> f64 {
+    if a == b && !a.is_empty() {
+        return 1.;
+    }
+    let a: String = a.chars().filter(|c| !c.is_whitespace()).collect();
+    let b: String = b.chars().filter(|c| !c.is_whitespace()).collect();
+    weighted_levenshtein_similarity(&a, &b)
+}
+
+/// The results of comparing two strings, as returned by [`levenshtein_report`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevenshteinReport {
+    /// The Levenshtein distance, as returned by [`levenshtein_distance`].
+    pub distance: usize,
+    /// The similarity ratio, as returned by [`levenshtein_similarity`].
+    pub similarity: f64,
+    /// The weighted similarity ratio, as returned by [`weighted_levenshtein_similarity`].
+    pub weighted_similarity: f64,
+}
+
+/// Computes the Levenshtein distance, the similarity ratio and the weighted similarity ratio
+/// between two strings, using a single matrix.
+///
+/// # Arguments
+///
+/// * `a` - The query string.
+/// * `b` - The value string to compare against.
+///
+/// # Returns
+///
+/// Returns a [`LevenshteinReport`] containing all three results.
+pub fn levenshtein_report(a: &str, b: &str) -> LevenshteinReport {
     let matrix = levenshtein_matrix(a, b);
-    weighted_edit_similarity(&matrix, a, b)
+    let len_a = a.chars().count();
+    let len_b = b.chars().count();
+    let distance = matrix[len_a][len_b];
+
+    let max_distance = len_a.max(len_b);
+    let similarity = if max_distance == 0 {
+        0.
+    } else {
+        (max_distance - distance) as f64 / max_distance as f64
+    };
+
+    let weighted_similarity = if a == b && !a.is_empty() {
+        1.
+    } else {
+        weighted_edit_similarity(&matrix, a, b, false)
+    };
+
+    LevenshteinReport {
+        distance,
+        similarity,
+        weighted_similarity,
+    }
 }
 
-/// Generates a matrix used to compute the Levenshtein distance between two strings.
+/// Generates a matrix used to compute the Levenshtein distance between the characters of two strings.
+/// It has a row for every char of `a` and a column for every char of `b`, plus a leading row and column.
 ///
 /// # Arguments
 ///
@@ -56,27 +579,37 @@ pub fn weighted_levenshtein_similarity(a: &str, b: &str) -> f64 {
 ///
 /// Returns a matrix (`Vec<Vec<usize>>`) representing the costs of edits required to change the first string into the second.
 pub fn levenshtein_matrix(a: &str, b: &str) -> Vec<Vec<usize>> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    levenshtein_matrix_generic(&a, &b)
+}
+
+/// Generates a Levenshtein matrix for two slices of arbitrary comparable elements.
+///
+/// # Arguments
+///
+/// * `a` - The first slice to compare.
+/// * `b` - The second slice to compare.
+///
+/// # Returns
+///
+/// Returns a matrix (`Vec<Vec<usize>>`) representing the costs of edits required to change the first slice into the second.
+pub fn levenshtein_matrix_generic<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Vec<usize>> {
     let len_a = a.len();
     let len_b = b.len();
 
-    // Create a matrix.
     let mut matrix = vec![vec![0; len_b + 1]; len_a + 1];
 
-    // Initialize the matrix.
-    for i in 0..=len_a {
-        matrix[i][0] = i;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=len_b {
-        matrix[0][j] = j;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
-    let a: Vec<char> = a.chars().collect();
-    let b: Vec<char> = b.chars().collect();
-
-    // Compute the Levenshtein distance.
     for i in 1..=len_a {
         for j in 1..=len_b {
-            let cost = if a.get(i - 1) == b.get(j - 1) { 0 } else { 1 };
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
 
             matrix[i][j] = std::cmp::min(
                 matrix[i - 1][j] + 1,
@@ -88,6 +621,75 @@ pub fn levenshtein_matrix(a: &str, b: &str) -> Vec<Vec<usize>> {
     matrix
 }
 
+/// Computes the similarity ratio based on the Levenshtein distance between two byte slices.
+///
+/// # Arguments
+///
+/// * `a` - The first byte slice to compare.
+/// * `b` - The second byte slice to compare.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn byte_levenshtein_similarity(a: &[u8], b: &[u8]) -> f64 {
+    let distance = levenshtein_matrix_generic(a, b)[a.len()][b.len()];
+    let max_distance = a.len().max(b.len());
+    if max_distance == 0 {
+        0.
+    } else {
+        (max_distance - distance) as f64 / max_distance as f64
+    }
+}
+
+/// Computes the Levenshtein distance between the extended grapheme clusters of two strings. \
+/// Unlike chars, a grapheme cluster keeps an emoji together with its modifiers,
+/// or a letter together with its combining marks, so changing a skin tone is a single edit.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns the Levenshtein distance in grapheme clusters as a `usize`.
+#[cfg(feature = "unicode-segmentation")]
+pub fn grapheme_levenshtein_distance(a: &str, b: &str) -> usize {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let a: Vec<&str> = a.graphemes(true).collect();
+    let b: Vec<&str> = b.graphemes(true).collect();
+    levenshtein_matrix_generic(&a, &b)[a.len()][b.len()]
+}
+
+/// Computes the similarity ratio based on the Levenshtein distance between the extended grapheme clusters of two strings.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+#[cfg(feature = "unicode-segmentation")]
+pub fn grapheme_levenshtein_similarity(a: &str, b: &str) -> f64 {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    if a == b && !a.is_empty() {
+        return 1.;
+    }
+    let a: Vec<&str> = a.graphemes(true).collect();
+    let b: Vec<&str> = b.graphemes(true).collect();
+    let distance = levenshtein_matrix_generic(&a, &b)[a.len()][b.len()];
+    let max_distance = a.len().max(b.len());
+    if max_distance == 0 {
+        0.
+    } else {
+        (max_distance - distance) as f64 / max_distance as f64
+    }
+}
+
 /// Represents an edit operation in the Levenshtein distance algorithm.
 #[derive(Debug)]
 pub(crate) enum EditOperation {
@@ -109,13 +711,13 @@ pub(crate) enum EditOperation {
 /// # Returns
 ///
 /// Returns a vector of `EditOperation` which are the steps needed to convert the original string into the target string.
-pub(crate) fn edit_operations(matrix: &Vec<Vec<usize>>, a: &str, b: &str) -> Vec<EditOperation> {
+pub(crate) fn edit_operations(matrix: &[Vec<usize>], a: &str, b: &str) -> Vec<EditOperation> {
     let mut operations = Vec::new();
-    let mut len_a = a.len();
-    let mut len_b = b.len();
 
     let a: Vec<char> = a.chars().collect();
     let b: Vec<char> = b.chars().collect();
+    let mut len_a = a.len();
+    let mut len_b = b.len();
 
     while len_a > 0 && len_b > 0 {
         let current = matrix[len_a][len_b];
@@ -128,10 +730,15 @@ pub(crate) fn edit_operations(matrix: &Vec<Vec<usize>>, a: &str, b: &str) -> Vec
                 1
             };
 
-        // No change needed, move diagonally without any operation
+        // No change needed, move diagonally over the whole run of unchanged chars
         if a.get(len_a - 1) == b.get(len_b - 1) {
-            len_a -= 1;
-            len_b -= 1;
+            let mut match_count = 0;
+            while len_a > 0 && len_b > 0 && a.get(len_a - 1) == b.get(len_b - 1) {
+                len_a -= 1;
+                len_b -= 1;
+                match_count += 1;
+            }
+            operations.push(EditOperation::None(match_count));
             continue;
         }
 
@@ -182,7 +789,17 @@ pub(crate) fn edit_operations(matrix: &Vec<Vec<usize>>, a: &str, b: &str) -> Vec
     operations
 }
 
-pub(crate) fn weighted_edit_similarity(matrix: &Vec<Vec<usize>>, a: &str, b: &str) -> f64 {
+pub(crate) fn weighted_edit_similarity(
+    matrix: &[Vec<usize>],
+    a: &str,
+    b: &str,
+    match_bonus: bool,
+) -> f64 {
+    weighted_ratio(weighted_edit_distance(matrix, a, b, match_bonus), a, b)
+}
+
+/// Sums the logarithmic costs of the edit operations of the matrix of `a` and `b`.
+fn weighted_edit_distance(matrix: &[Vec<usize>], a: &str, b: &str, match_bonus: bool) -> f64 {
     let ops = edit_operations(matrix, a, b);
 
     let mut distance = 0.;
@@ -195,11 +812,16 @@ pub(crate) fn weighted_edit_similarity(matrix: &Vec<Vec<usize>>, a: &str, b: &st
                 distance += (len_a as f64).ln_1p();
                 distance += (len_b as f64).ln_1p();
             }
-            EditOperation::None(len_a) => distance -= (len_a as f64).ln_1p(),
+            EditOperation::None(len_a) if match_bonus => distance -= (len_a as f64).ln_1p(),
+            EditOperation::None(_) => {}
         }
     }
+    distance
+}
 
-    let max_distance = a.len().max(b.len());
+/// Turns a weighted edit distance into a similarity ratio, relative to the longer of both strings.
+fn weighted_ratio(distance: f64, a: &str, b: &str) -> f64 {
+    let max_distance = a.chars().count().max(b.chars().count());
     if max_distance == 0 {
         0.
     } else {
@@ -213,3 +835,21 @@ pub fn common_prefix(a: &str, b: &str) -> usize {
         .take_while(|(c1, c2)| c1 == c2)
         .count()
 }
+
+/// Counts the equal chars at the end of two strings, the counterpart of [`common_prefix`].
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns the length of the common suffix in chars.
+pub fn common_suffix(a: &str, b: &str) -> usize {
+    a.chars()
+        .rev()
+        .zip(b.chars().rev())
+        .take_while(|(c1, c2)| c1 == c2)
+        .count()
+}
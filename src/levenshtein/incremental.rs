@@ -2,16 +2,80 @@
 //! efficiently computing Levenshtein distances and similarity scores for scenarios where
 //! the 'query' string is subject to incremental changes.
 
-use crate::levenshtein::base::{levenshtein_matrix, weighted_edit_similarity};
+use std::borrow::Cow;
+
+use crate::levenshtein::base::{
+    common_suffix, levenshtein_matrix_generic, weighted_edit_similarity,
+    weighted_levenshtein_similarity,
+};
+use crate::search_engine::{scores_of, sort_descending, Mutability, SearchEngine, SearchTrace};
+use crate::similarity::Similarity;
 
 /// A structure for incrementally calculating Levenshtein distances and similarities.
 /// This is particularly efficient when repeatedly comparing slight variations of the query
 /// against a constant data string.
+///
+/// With the `serde` feature it can be serialized, including its matrix, so it doesn't have to be rebuilt.
+/// Deserializing fails, if the dimensions of the matrix don't match the query and data strings.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Snapshot"))]
 pub struct IncrementalLevenshtein {
     query: String,
     data: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    query_chars: Vec<char>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    data_chars: Vec<char>,
     matrix: Vec<Vec<usize>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    valid_rows: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    recomputed_cells: usize,
+    case_insensitive: bool,
+    reversed: bool,
+}
+
+/// The serialized fields of an [`IncrementalLevenshtein`], which are validated before the caches are rebuilt from them.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Snapshot {
+    query: String,
+    data: String,
+    matrix: Vec<Vec<usize>>,
+    case_insensitive: bool,
+    reversed: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Snapshot> for IncrementalLevenshtein {
+    type Error = String;
+
+    fn try_from(snapshot: Snapshot) -> Result<Self, Self::Error> {
+        let query_chars: Vec<char> = snapshot.query.chars().collect();
+        let data_chars: Vec<char> = snapshot.data.chars().collect();
+        let (rows, columns) = (query_chars.len() + 1, data_chars.len() + 1);
+        if snapshot.matrix.len() != rows || snapshot.matrix.iter().any(|row| row.len() != columns) {
+            return Err(format!(
+                "the matrix of a query with {} and data with {} chars must have {rows} rows of {columns} cells",
+                query_chars.len(),
+                data_chars.len(),
+            ));
+        }
+        // The rows of a common suffix may have been skipped by the last similarity calculation.
+        let valid_rows = rows - common_suffix(&snapshot.query, &snapshot.data);
+        Ok(Self {
+            query: snapshot.query,
+            data: snapshot.data,
+            query_chars,
+            data_chars,
+            matrix: snapshot.matrix,
+            valid_rows,
+            recomputed_cells: 0,
+            case_insensitive: snapshot.case_insensitive,
+            reversed: snapshot.reversed,
+        })
+    }
 }
 
 impl IncrementalLevenshtein {
@@ -23,10 +87,71 @@ impl IncrementalLevenshtein {
     /// * `query` - A slice of the query string.
     /// * `data` - A slice of the data string.
     pub fn new(query: &str, data: &str) -> Self {
+        Self::from_normalized(query.to_string(), data.to_string(), false, false)
+    }
+
+    /// Constructs a new `IncrementalLevenshtein`, which stores the query and data strings reversed. \
+    /// Rows are reused for a shared prefix of the queries, which makes appending to the query cheap.
+    /// Reversed, prepending to the query becomes the cheap case instead, e.g. for users typing at the front.
+    /// The Levenshtein distance doesn't change by reversing both strings,
+    /// so [similarity](IncrementalLevenshtein::similarity) reports the same results as the non-reversed version.
+    /// [weighted_similarity](IncrementalLevenshtein::weighted_similarity) depends on the direction of the edits,
+    /// so it is computed from scratch in the original direction to report the same results as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice of the query string.
+    /// * `data` - A slice of the data string.
+    pub fn new_reversed(query: &str, data: &str) -> Self {
+        let query: String = query.chars().rev().collect();
+        let data: String = data.chars().rev().collect();
+        Self::from_normalized(query, data, false, true)
+    }
+
+    /// Constructs a new `IncrementalLevenshtein` ignoring the case of the query and data strings.
+    /// Both strings are lowercased, and every new query is lowercased the same way before the matrix is updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice of the query string.
+    /// * `data` - A slice of the data string.
+    pub fn new_case_insensitive(query: &str, data: &str) -> Self {
+        Self::from_normalized(query.to_lowercase(), data.to_lowercase(), true, false)
+    }
+
+    /// Private constructor for query and data strings, which are already normalized.
+    fn from_normalized(
+        query: String,
+        data: String,
+        case_insensitive: bool,
+        reversed: bool,
+    ) -> Self {
+        let query_chars: Vec<char> = query.chars().collect();
+        let data_chars: Vec<char> = data.chars().collect();
         Self {
-            query: query.to_string(),
-            data: data.to_string(),
-            matrix: levenshtein_matrix(query, data),
+            matrix: levenshtein_matrix_generic(&query_chars, &data_chars),
+            valid_rows: query_chars.len() + 1,
+            recomputed_cells: 0,
+            query,
+            data,
+            query_chars,
+            data_chars,
+            case_insensitive,
+            reversed,
+        }
+    }
+
+    /// Private method to apply the same normalization to a new query, that was applied to the data string.
+    fn normalize<'a>(&self, query: &'a str) -> Cow<'a, str> {
+        let query = if self.case_insensitive {
+            Cow::Owned(query.to_lowercase())
+        } else {
+            Cow::Borrowed(query)
+        };
+        if self.reversed {
+            Cow::Owned(query.chars().rev().collect())
+        } else {
+            query
         }
     }
 
@@ -41,8 +166,9 @@ impl IncrementalLevenshtein {
     ///
     /// A `usize` value indicating the count of identical leading characters.
     fn query_similarity(&mut self, new_query: &str) -> usize {
-        self.query
-            .chars()
+        self.query_chars
+            .iter()
+            .copied()
             .zip(new_query.chars())
             .take_while(|(c1, c2)| c1 == c2)
             .count()
@@ -50,45 +176,70 @@ impl IncrementalLevenshtein {
 
     /// Updates the Levenshtein matrix based on the new query string.
     /// This method should be called before calculating similarity if the query has changed.
+    /// The new query is normalized before its common prefix with the current query is determined.
+    ///
+    /// Rows shared with the previous query are reused. Unless `full` is set, the rows of a common suffix
+    /// of the query and the data are skipped as well, as they don't change the distance.
     ///
     /// # Arguments
     ///
     /// * `new_query` - A slice of the new query string.
-    fn update(&mut self, new_query: &str) {
+    /// * `full` - Whether all rows of the matrix are required.
+    ///
+    /// # Returns
+    ///
+    /// The length of the common suffix, whose rows were skipped.
+    fn update(&mut self, new_query: &str, full: bool) -> usize {
+        let new_query = self.normalize(new_query);
+        let new_query = new_query.as_ref();
         let query_similarity = self.query_similarity(new_query);
 
-        if new_query.len() > self.query.len() {
-            for _ in 0..(new_query.len() - self.query.len()) {
-                let row = vec![0; self.data.len() + 1];
-                self.matrix.push(row);
-            }
-        } else {
-            for _ in 0..(self.query.len() - new_query.len()) {
-                self.matrix.pop();
-            }
-        }
+        self.query.clear();
+        self.query.push_str(new_query);
+        self.query_chars.clear();
+        self.query_chars.extend(new_query.chars());
 
-        self.query = new_query.to_string();
+        let a = &self.query_chars;
+        let b = &self.data_chars;
+        let matrix = &mut self.matrix;
+        let len_a = a.len();
+        let len_b = b.len();
 
-        let a: Vec<char> = self.query.chars().collect();
-        let b: Vec<char> = self.data.chars().collect();
-        let len_a = self.query.len();
-        let len_b = self.data.len();
+        // The matrix has a row for every char of the query.
+        matrix.resize_with(len_a + 1, || vec![0; len_b + 1]);
 
-        for i in query_similarity..=len_a {
-            self.matrix[i][0] = i;
+        let suffix = if full {
+            0
+        } else {
+            common_suffix(&self.query, &self.data)
+        };
+        let last_row = len_a - suffix;
+        let reused_rows = (query_similarity + 1).min(self.valid_rows);
+
+        for (i, row) in matrix
+            .iter_mut()
+            .enumerate()
+            .take(last_row + 1)
+            .skip(reused_rows)
+        {
+            row[0] = i;
         }
 
-        for i in query_similarity.max(1)..=len_a {
+        self.recomputed_cells = 0;
+        for i in reused_rows.max(1)..=last_row {
             for j in 1..=len_b {
-                let cost = if a.get(i - 1) == b.get(j - 1) { 0 } else { 1 };
+                let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
 
-                self.matrix[i][j] = std::cmp::min(
-                    self.matrix[i - 1][j] + 1,
-                    std::cmp::min(self.matrix[i][j - 1] + 1, self.matrix[i - 1][j - 1] + cost),
+                matrix[i][j] = std::cmp::min(
+                    matrix[i - 1][j] + 1,
+                    std::cmp::min(matrix[i][j - 1] + 1, matrix[i - 1][j - 1] + cost),
                 );
             }
+            self.recomputed_cells += len_b;
         }
+
+        self.valid_rows = reused_rows.max(last_row + 1);
+        suffix
     }
 
     /// Calculates the similarity ratio between the stored data string and the new query string
@@ -102,9 +253,12 @@ impl IncrementalLevenshtein {
     ///
     /// A `f64` representing the similarity ratio (0.0 meaning no similarity and 1.0 meaning identical).
     pub fn similarity(&mut self, new_query: &str) -> f64 {
-        self.update(new_query);
-        let distance = self.matrix[self.query.len()][self.data.len()];
-        let max_distance = self.query.len().max(self.data.len());
+        // Stripping a common suffix from both strings doesn't change their distance.
+        let suffix = self.update(new_query, false);
+        let len_a = self.query_chars.len();
+        let len_b = self.data_chars.len();
+        let distance = self.matrix[len_a - suffix][len_b - suffix];
+        let max_distance = len_a.max(len_b);
         if max_distance == 0 {
             0.
         } else {
@@ -114,6 +268,8 @@ impl IncrementalLevenshtein {
 
     /// Calculates a weighted similarity ratio, which considers the length and type of edit
     /// operations required to convert the query into the data string.
+    /// The result is identical to
+    /// [`weighted_levenshtein_similarity(new_query, data)`](crate::levenshtein::base::weighted_levenshtein_similarity).
     ///
     /// # Arguments
     ///
@@ -123,11 +279,119 @@ impl IncrementalLevenshtein {
     ///
     /// A `f64` representing the weighted similarity ratio.
     pub fn weighted_similarity(&mut self, new_query: &str) -> f64 {
-        self.update(new_query);
-        weighted_edit_similarity(&self.matrix, &self.query, &self.data)
+        if self.reversed {
+            self.update(new_query, false);
+            let query: String = self.query.chars().rev().collect();
+            let data: String = self.data.chars().rev().collect();
+            return weighted_levenshtein_similarity(&query, &data);
+        }
+        self.update(new_query, true);
+        weighted_edit_similarity(&self.matrix, &self.query, &self.data, false)
+    }
+
+    /// Returns the number of cells of the Levenshtein matrix, which is `(query_len + 1) * (data_len + 1)`.
+    pub fn matrix_cells(&self) -> usize {
+        self.matrix.iter().map(Vec::len).sum()
+    }
+
+    /// Minimizes the memory footprint, by dropping all rows of the matrix but the first. \
+    /// Every row but the first belongs to a char of the query, so if there are rows to drop,
+    /// the query is reset to the empty string and the rows are rebuilt by the next similarity calculation.
+    /// If the query already is empty, only unused capacity is released and no progress is lost.
+    pub fn compact(&mut self) {
+        if self.matrix.len() > 1 {
+            self.query.clear();
+            self.query_chars.clear();
+            self.matrix.truncate(1);
+            self.valid_rows = 1;
+        }
+        self.query.shrink_to_fit();
+        self.query_chars.shrink_to_fit();
+        self.matrix.shrink_to_fit();
+    }
+
+    /// Returns the number of matrix cells computed by the last similarity calculation,
+    /// which shows how much work was saved by reusing rows.
+    pub fn recomputed_cells(&self) -> usize {
+        self.recomputed_cells
+    }
+
+    /// Returns the query of the last similarity calculation. \
+    /// It is stored normalized, so it is lowercased if the struct is [case insensitive](IncrementalLevenshtein::new_case_insensitive)
+    /// and reversed if it is [reversed](IncrementalLevenshtein::new_reversed).
+    pub fn query(&self) -> &str {
+        &self.query
     }
 
+    /// Returns the data string, normalized the same way as the [query](IncrementalLevenshtein::query).
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Returns the Levenshtein matrix of the current query and the data string. \
+    /// After [similarity](IncrementalLevenshtein::similarity), the rows of a common suffix of the query
+    /// and the data may not have been computed, while
+    /// [weighted_similarity](IncrementalLevenshtein::weighted_similarity) always computes the whole matrix. \
+    /// If the strings are [reversed](IncrementalLevenshtein::new_reversed), so is the matrix.
     pub fn matrix(&self) -> &Vec<Vec<usize>> {
         &self.matrix
     }
 }
+
+impl<Value, S, M: Mutability> SearchEngine<Value, str, S, M>
+where
+    Value: AsRef<str>,
+    S: Similarity<Value, str>,
+{
+    /// Creates a pool of [`IncrementalLevenshtein`] structs for [similarities_pooled](SearchEngine::similarities_pooled),
+    /// containing one for every value, in insertion order.
+    pub fn new_pool(&self) -> Vec<IncrementalLevenshtein> {
+        self.values()
+            .map(|value| IncrementalLevenshtein::new("", value.as_ref()))
+            .collect()
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their weighted similarity scores
+    /// to the given query, with the most similar value first, using an external pool of [`IncrementalLevenshtein`] structs. \
+    /// The search engine doesn't need mutable access, so the pool can be owned by the caller
+    /// and reused across requests or threads. The configured similarity functions are not used.
+    ///
+    /// The pool must contain exactly one [`IncrementalLevenshtein`] per value, in insertion order,
+    /// as created by [new_pool](SearchEngine::new_pool).
+    /// If values are added or removed, the pool has to be recreated.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `pool` - The pool of [`IncrementalLevenshtein`] structs, one per value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the pool doesn't match the number of values.
+    pub fn similarities_pooled(
+        &self,
+        query: &str,
+        pool: &mut [IncrementalLevenshtein],
+    ) -> Vec<(&Value, f64)> {
+        assert_eq!(
+            pool.len(),
+            self.values().len(),
+            "the pool must contain one IncrementalLevenshtein per value"
+        );
+
+        let trace = SearchTrace::start(query, self.len());
+        let mut values: Vec<_> = self
+            .values()
+            .zip(pool.iter_mut())
+            .map(|(value, incremental)| (value, incremental.weighted_similarity(query)))
+            .collect();
+        sort_descending(&mut values);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+}
@@ -1,4 +1,6 @@
 //! This module defines functions and data structures for calculating the Levenshtein distance
 //! and similarity between two strings, including an incremental version.
+pub mod alignment;
 pub mod base;
 pub mod incremental;
+pub mod partial;
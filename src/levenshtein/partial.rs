@@ -0,0 +1,64 @@
+//! This module defines the partial ratio, which compares the query to the best matching substring of a value,
+//! so a short query isn't penalized for the rest of a long value.
+
+use std::ops::Range;
+
+use crate::levenshtein::base::normalized_levenshtein_similarity;
+
+/// Computes the similarity of the query to its best matching substring of the value. \
+/// Every substring of the value with as many chars as the query is compared with
+/// [`normalized_levenshtein_similarity`]. If the value is not longer than the query, the whole value is compared.
+///
+/// # Arguments
+///
+/// * `value` - The value to search for the query in.
+/// * `query` - The query string.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio of the best matching substring.
+pub fn partial_ratio(value: &str, query: &str) -> f64 {
+    partial_match_span(value, query).1
+}
+
+/// Finds the substring of the value, which matches the query best, like [`partial_ratio`]. \
+/// The returned range always lies on char boundaries, so it can be used to slice the value,
+/// e.g. to highlight the match. Of multiple equally good substrings the first one is returned.
+///
+/// # Arguments
+///
+/// * `value` - The value to search for the query in.
+/// * `query` - The query string.
+///
+/// # Returns
+///
+/// Returns a tuple of the byte range of the best matching substring in the value and its similarity ratio.
+pub fn partial_match_span(value: &str, query: &str) -> (Range<usize>, f64) {
+    let query_len = query.chars().count();
+    let bounds: Vec<usize> = value
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain(std::iter::once(value.len()))
+        .collect();
+    let value_len = bounds.len() - 1;
+
+    if value_len <= query_len {
+        return (
+            0..value.len(),
+            normalized_levenshtein_similarity(query, value),
+        );
+    }
+
+    let mut best = (0..0, f64::NEG_INFINITY);
+    for start in 0..=value_len - query_len {
+        let range = bounds[start]..bounds[start + query_len];
+        let similarity = normalized_levenshtein_similarity(query, &value[range.clone()]);
+        if similarity > best.1 {
+            best = (range, similarity);
+            if similarity == 1. {
+                break;
+            }
+        }
+    }
+    best
+}
@@ -8,7 +8,7 @@
 //!fn main() {
 //!     let engine = SearchEngine::new()
 //!         .with_values(vec!["hello", "world", "foo", "bar"])
-//!         .with(|v, q| weighted_levenshtein_similarity(v, q));
+//!         .with(|v, q| weighted_levenshtein_similarity(q, v));
 //!
 //!     let results = engine.search("hallo");
 //!
@@ -133,10 +133,13 @@
 //! use simple_search::search_engine::SearchEngine;
 //! use simple_search::levenshtein::base::weighted_levenshtein_similarity;
 //!
+//! # #[cfg(not(feature = "rayon"))]
+//! # fn main() {}
+//! # #[cfg(feature = "rayon")]
 //!fn main() {
 //!     let engine = SearchEngine::new()
 //!         .with_values(vec!["hello", "world", "foo", "bar"])
-//!         .with(|v, q| weighted_levenshtein_similarity(v, q));
+//!         .with(|v, q| weighted_levenshtein_similarity(q, v));
 //!
 //!     let results = engine.par_search("hallo");
 //!
@@ -144,9 +147,27 @@
 //!}
 //! ```
 
+pub mod automaton;
+pub mod eval;
+pub mod explain;
+pub mod expr;
+pub mod highlight;
+pub mod hybrid;
+pub mod idf;
+#[cfg(feature = "serde_json")]
+pub mod json;
 pub mod levenshtein;
+mod macros;
+pub mod metrics;
 pub mod search_engine;
+pub mod searchable;
+pub mod session;
+pub mod shared;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+pub mod tiered;
 
 #[doc(hidden)]
 pub mod similarity;
 pub mod type_erasure;
+pub mod validate;
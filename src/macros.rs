@@ -0,0 +1,53 @@
+//! This module provides the [`search_engine!`](crate::search_engine!) macro,
+//! which builds a search engine over the fields of a struct.
+
+/// Builds a [`SearchEngine`](crate::search_engine::SearchEngine) comparing the query against fields of the values.
+///
+/// Every line `field => Metric @ weight` adds one similarity function with the given weight:
+/// * `IncrementalLevenshtein` adds a stateful function, keeping an
+///   [`IncrementalLevenshtein`](crate::levenshtein::incremental::IncrementalLevenshtein) per value,
+///   that computes the weighted similarity.
+/// * Any other path names a stateless function, which is called as `metric(query, &value.field)`.
+///
+/// The functions are added in the order they are listed, which is the same order
+/// used by [`set_weight`](crate::search_engine::SearchEngine::set_weight).
+///
+/// ```rust
+/// use simple_search::search_engine;
+/// use simple_search::levenshtein::base::weighted_levenshtein_similarity;
+///
+/// struct Book {
+///     title: String,
+///     author: String,
+/// }
+///
+/// let books = vec![Book { title: "Dune".to_string(), author: "Frank Herbert".to_string() }];
+///
+/// let mut engine = search_engine! {
+///     values: books,
+///     title => IncrementalLevenshtein @ 1.0,
+///     author => weighted_levenshtein_similarity @ 0.8,
+/// };
+///
+/// assert_eq!(engine.search("Dune")[0].title, "Dune");
+/// ```
+#[macro_export]
+macro_rules! search_engine {
+    (@layer $engine:expr, $field:ident => IncrementalLevenshtein @ $weight:expr) => {
+        $engine.with_state_and_weight(
+            $weight,
+            |value| $crate::levenshtein::incremental::IncrementalLevenshtein::new("", &value.$field),
+            |state, _, query| state.weighted_similarity(query),
+        )
+    };
+    (@layer $engine:expr, $field:ident => $($metric:ident)::+ @ $weight:expr) => {
+        $engine.with_weight($weight, |value, query| $($metric)::+(query, &value.$field))
+    };
+    (values: $values:expr $(, $field:ident => $($metric:ident)::+ @ $weight:expr)* $(,)?) => {{
+        let engine = $crate::search_engine::SearchEngine::new().with_values($values);
+        $(
+            let engine = $crate::search_engine!(@layer engine, $field => $($metric)::+ @ $weight);
+        )*
+        engine
+    }};
+}
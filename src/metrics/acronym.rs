@@ -0,0 +1,61 @@
+//! This module defines functions for matching a query against the word-boundary initials of a value,
+//! which is useful for searching CamelCase or snake_case identifiers.
+
+use crate::levenshtein::base::normalized_levenshtein_similarity;
+
+/// Extracts the chars at the start of every word of `value`.
+///
+/// A new word starts after a separator (any char that is not alphanumeric),
+/// at a transition from lowercase to uppercase, before the last uppercase char of an uppercase run followed by lowercase,
+/// and at transitions between letters and digits.
+///
+/// # Arguments
+///
+/// * `value` - The value to extract the initials from.
+///
+/// # Returns
+///
+/// Returns the initials of all words in order.
+pub fn word_initials(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let mut initials = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            continue;
+        }
+
+        let is_boundary = match i.checked_sub(1).map(|p| chars[p]) {
+            None => true,
+            Some(previous) if !previous.is_alphanumeric() => true,
+            Some(previous) if previous.is_numeric() != c.is_numeric() => true,
+            Some(previous) if previous.is_lowercase() && c.is_uppercase() => true,
+            Some(previous) => {
+                previous.is_uppercase()
+                    && c.is_uppercase()
+                    && chars.get(i + 1).is_some_and(|next| next.is_lowercase())
+            }
+        };
+
+        if is_boundary {
+            initials.push(c);
+        }
+    }
+    initials
+}
+
+/// Computes how well the query matches the word-boundary initials of the value, ignoring case.
+/// For example the query `"gBN"` matches `"getBookName"` perfectly.
+///
+/// # Arguments
+///
+/// * `value` - The value whose initials are matched.
+/// * `query` - The query to match against the initials.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn acronym_similarity(value: &str, query: &str) -> f64 {
+    let initials = word_initials(value).to_lowercase();
+    normalized_levenshtein_similarity(&query.to_lowercase(), &initials)
+}
@@ -0,0 +1,92 @@
+//! This module defines helpers for ignoring leading articles, as done when sorting library titles,
+//! so "Great Gatsby" matches "The Great Gatsby".
+
+/// The articles removed by [`strip_leading_articles`].
+pub const DEFAULT_ARTICLES: &[&str] = &["the", "a", "an"];
+
+/// Removes a leading article from a string, using [`DEFAULT_ARTICLES`].
+///
+/// # Arguments
+///
+/// * `s` - The string to strip.
+///
+/// # Returns
+///
+/// Returns the string without its leading article and the whitespace following it.
+pub fn strip_leading_articles(s: &str) -> &str {
+    strip_leading_articles_with(s, DEFAULT_ARTICLES)
+}
+
+/// Removes a leading article from a string. \
+/// An article is only removed if it is followed by whitespace, ignoring case,
+/// so "Theory" and a title consisting only of "The" are kept.
+///
+/// # Arguments
+///
+/// * `s` - The string to strip.
+/// * `articles` - The articles to remove.
+///
+/// # Returns
+///
+/// Returns the string without its leading article and the whitespace following it.
+pub fn strip_leading_articles_with<'a, A: AsRef<str>>(s: &'a str, articles: &[A]) -> &'a str {
+    let trimmed = s.trim_start();
+    let Some((first, rest)) = trimmed.split_once(char::is_whitespace) else {
+        return s;
+    };
+
+    if articles
+        .iter()
+        .any(|article| article.as_ref().eq_ignore_ascii_case(first))
+    {
+        rest.trim_start()
+    } else {
+        s
+    }
+}
+
+/// Wraps a metric, so leading [`DEFAULT_ARTICLES`] are removed from both of its arguments first.
+///
+/// # Arguments
+///
+/// * `metric` - The metric to wrap.
+///
+/// # Returns
+///
+/// Returns a metric ignoring leading articles.
+pub fn article_insensitive<Metric>(metric: Metric) -> impl Fn(&str, &str) -> f64
+where
+    Metric: Fn(&str, &str) -> f64,
+{
+    article_insensitive_with(DEFAULT_ARTICLES, metric)
+}
+
+/// Wraps a metric, so the given leading articles are removed from both of its arguments first.
+///
+/// # Arguments
+///
+/// * `articles` - The articles to remove.
+/// * `metric` - The metric to wrap.
+///
+/// # Returns
+///
+/// Returns a metric ignoring leading articles.
+pub fn article_insensitive_with<A, Metric>(
+    articles: impl IntoIterator<Item = A>,
+    metric: Metric,
+) -> impl Fn(&str, &str) -> f64
+where
+    A: AsRef<str>,
+    Metric: Fn(&str, &str) -> f64,
+{
+    let articles: Vec<String> = articles
+        .into_iter()
+        .map(|article| article.as_ref().to_string())
+        .collect();
+    move |a, b| {
+        metric(
+            strip_leading_articles_with(a, &articles),
+            strip_leading_articles_with(b, &articles),
+        )
+    }
+}
@@ -0,0 +1,203 @@
+//! This module defines opt-in normalization steps, that are applied to both arguments of a metric,
+//! so "Cafe" matches "café" and "CAFÉ". All other metrics compare their arguments unchanged.
+
+use crate::levenshtein::base::normalized_levenshtein_similarity;
+
+/// Maps the precomposed Latin letters with diacritics to the letter without them.
+const DIACRITICS: &[(&str, char)] = &[
+    ("àáâãäåāăą", 'a'),
+    ("ÀÁÂÃÄÅĀĂĄ", 'A'),
+    ("çćĉċč", 'c'),
+    ("ÇĆĈĊČ", 'C'),
+    ("ďđ", 'd'),
+    ("ĎĐ", 'D'),
+    ("èéêëēĕėęě", 'e'),
+    ("ÈÉÊËĒĔĖĘĚ", 'E'),
+    ("ĝğġģ", 'g'),
+    ("ĜĞĠĢ", 'G'),
+    ("ĥħ", 'h'),
+    ("ĤĦ", 'H'),
+    ("ìíîïĩīĭį", 'i'),
+    ("ÌÍÎÏĨĪĬĮİ", 'I'),
+    ("ĵ", 'j'),
+    ("Ĵ", 'J'),
+    ("ķ", 'k'),
+    ("Ķ", 'K'),
+    ("ĺļľŀł", 'l'),
+    ("ĹĻĽĿŁ", 'L'),
+    ("ñńņňŉ", 'n'),
+    ("ÑŃŅŇ", 'N'),
+    ("òóôõöøōŏő", 'o'),
+    ("ÒÓÔÕÖØŌŎŐ", 'O'),
+    ("ŕŗř", 'r'),
+    ("ŔŖŘ", 'R'),
+    ("śŝşš", 's'),
+    ("ŚŜŞŠ", 'S'),
+    ("ţťŧ", 't'),
+    ("ŢŤŦ", 'T'),
+    ("ùúûüũūŭůűų", 'u'),
+    ("ÙÚÛÜŨŪŬŮŰŲ", 'U'),
+    ("ŵ", 'w'),
+    ("Ŵ", 'W'),
+    ("ýÿŷ", 'y'),
+    ("ÝŶŸ", 'Y'),
+    ("źżž", 'z'),
+    ("ŹŻŽ", 'Z'),
+];
+
+/// Whether the char is a combining diacritical mark, as used by decomposed strings like "e\u{301}".
+fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{300}'..='\u{36F}')
+}
+
+/// Lowercases a string, so strings differing only in case compare equal.
+///
+/// # Arguments
+///
+/// * `s` - The string to fold.
+///
+/// # Returns
+///
+/// Returns the lowercased string.
+pub fn fold_case(s: &str) -> String {
+    s.to_lowercase()
+}
+
+/// Removes the diacritics of Latin letters, both from precomposed chars like 'é'
+/// and decomposed ones like "e\u{301}". Chars of other scripts are kept unchanged.
+///
+/// # Arguments
+///
+/// * `s` - The string to strip.
+///
+/// # Returns
+///
+/// Returns the string without diacritics.
+pub fn strip_diacritics(s: &str) -> String {
+    s.chars()
+        .filter(|&c| !is_combining_mark(c))
+        .map(|c| {
+            if c.is_ascii() {
+                return c;
+            }
+            DIACRITICS
+                .iter()
+                .find(|(letters, _)| letters.contains(c))
+                .map_or(c, |(_, letter)| *letter)
+        })
+        .collect()
+}
+
+/// Composes a string into the Unicode normalization form C,
+/// so equal text compares equal regardless of whether it was entered precomposed or decomposed.
+/// It is only available with the `unicode-normalization` feature.
+///
+/// # Arguments
+///
+/// * `s` - The string to normalize.
+///
+/// # Returns
+///
+/// Returns the NFC normalized string.
+#[cfg(feature = "unicode-normalization")]
+pub fn nfc(s: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+
+    s.nfc().collect()
+}
+
+/// Computes the [`normalized_levenshtein_similarity`] of two strings, ignoring their case and diacritics.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn caseless_levenshtein_similarity(a: &str, b: &str) -> f64 {
+    Normalizer::new()
+        .fold_case()
+        .strip_diacritics()
+        .wrap(normalized_levenshtein_similarity)(a, b)
+}
+
+/// A configurable set of normalization steps, that are applied to both arguments of a metric. \
+/// The steps run in a fixed order: NFC composition, then case folding and finally diacritic stripping.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Normalizer {
+    fold_case: bool,
+    strip_diacritics: bool,
+    #[cfg(feature = "unicode-normalization")]
+    nfc: bool,
+}
+
+impl Normalizer {
+    /// Creates a normalizer, which doesn't change strings until steps are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds [`fold_case`] to the normalization steps.
+    pub fn fold_case(self) -> Self {
+        Self {
+            fold_case: true,
+            ..self
+        }
+    }
+
+    /// Adds [`strip_diacritics`] to the normalization steps.
+    pub fn strip_diacritics(self) -> Self {
+        Self {
+            strip_diacritics: true,
+            ..self
+        }
+    }
+
+    /// Adds [`nfc`] to the normalization steps.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn nfc(self) -> Self {
+        Self { nfc: true, ..self }
+    }
+
+    /// Applies all configured steps to a string.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The string to normalize.
+    ///
+    /// # Returns
+    ///
+    /// Returns the normalized string.
+    pub fn normalize(&self, s: &str) -> String {
+        let mut s = s.to_string();
+        #[cfg(feature = "unicode-normalization")]
+        if self.nfc {
+            s = nfc(&s);
+        }
+        if self.fold_case {
+            s = fold_case(&s);
+        }
+        if self.strip_diacritics {
+            s = strip_diacritics(&s);
+        }
+        s
+    }
+
+    /// Wraps a metric, so both of its arguments are normalized first.
+    ///
+    /// # Arguments
+    ///
+    /// * `metric` - The metric to wrap.
+    ///
+    /// # Returns
+    ///
+    /// Returns a metric comparing the normalized strings.
+    pub fn wrap<Metric>(self, metric: Metric) -> impl Fn(&str, &str) -> f64
+    where
+        Metric: Fn(&str, &str) -> f64,
+    {
+        move |a, b| metric(&self.normalize(a), &self.normalize(b))
+    }
+}
@@ -0,0 +1,106 @@
+//! This module defines a folding step for Unicode confusables,
+//! such as the Cyrillic 'а' that looks identical to the Latin 'a'.
+
+/// Maps common confusable chars to the Latin char they are mistaken for.
+const CONFUSABLES: &[(char, char)] = &[
+    // Cyrillic
+    ('а', 'a'),
+    ('в', 'b'),
+    ('с', 'c'),
+    ('е', 'e'),
+    ('һ', 'h'),
+    ('і', 'i'),
+    ('ј', 'j'),
+    ('к', 'k'),
+    ('м', 'm'),
+    ('н', 'h'),
+    ('о', 'o'),
+    ('р', 'p'),
+    ('ѕ', 's'),
+    ('т', 't'),
+    ('у', 'y'),
+    ('х', 'x'),
+    ('ԁ', 'd'),
+    ('ԛ', 'q'),
+    ('ԝ', 'w'),
+    ('А', 'A'),
+    ('В', 'B'),
+    ('С', 'C'),
+    ('Е', 'E'),
+    ('Н', 'H'),
+    ('І', 'I'),
+    ('Ј', 'J'),
+    ('К', 'K'),
+    ('М', 'M'),
+    ('О', 'O'),
+    ('Р', 'P'),
+    ('Ѕ', 'S'),
+    ('Т', 'T'),
+    ('Х', 'X'),
+    ('Ү', 'Y'),
+    // Greek
+    ('α', 'a'),
+    ('ο', 'o'),
+    ('ρ', 'p'),
+    ('ν', 'v'),
+    ('ι', 'i'),
+    ('Α', 'A'),
+    ('Β', 'B'),
+    ('Ε', 'E'),
+    ('Ζ', 'Z'),
+    ('Η', 'H'),
+    ('Ι', 'I'),
+    ('Κ', 'K'),
+    ('Μ', 'M'),
+    ('Ν', 'N'),
+    ('Ο', 'O'),
+    ('Ρ', 'P'),
+    ('Τ', 'T'),
+    ('Υ', 'Y'),
+    ('Χ', 'X'),
+    // Digits and symbols
+    ('０', '0'),
+    ('１', '1'),
+    ('ℓ', 'l'),
+    ('ı', 'i'),
+];
+
+/// Folds a single char to its canonical Latin form, or returns it unchanged if it isn't a known confusable.
+fn fold_char(c: char) -> char {
+    if c.is_ascii() {
+        return c;
+    }
+    CONFUSABLES
+        .iter()
+        .find(|(confusable, _)| *confusable == c)
+        .map_or(c, |(_, latin)| *latin)
+}
+
+/// Replaces all chars of the built-in confusable table with the Latin char they resemble.
+///
+/// # Arguments
+///
+/// * `s` - The string to fold.
+///
+/// # Returns
+///
+/// Returns the folded string.
+pub fn fold_confusables(s: &str) -> String {
+    s.chars().map(fold_char).collect()
+}
+
+/// Wraps a metric, so both of its arguments are passed through [`fold_confusables`] first.
+///
+/// # Arguments
+///
+/// * `metric` - The metric to wrap.
+///
+/// # Returns
+///
+/// Returns a metric that treats confusable chars as the Latin chars they resemble.
+pub fn confusable_insensitive<Metric>(metric: Metric) -> impl Fn(&str, &str) -> f64
+where
+    Metric: Fn(&str, &str) -> f64,
+{
+    move |a, b| metric(&fold_confusables(a), &fold_confusables(b))
+}
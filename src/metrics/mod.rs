@@ -0,0 +1,8 @@
+//! This module defines similarity metrics beyond the plain edit distance of the [`levenshtein`](crate::levenshtein) module, \
+//! such as n-grams, phonetic codes or acronyms. Some of them compare their normalized strings with the Levenshtein distance.
+pub mod acronym;
+pub mod articles;
+pub mod caseless;
+pub mod confusables;
+pub mod ngram;
+pub mod phonetic;
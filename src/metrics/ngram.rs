@@ -0,0 +1,126 @@
+//! This module defines similarity metrics based on the chars or n-grams two strings have in common,
+//! regardless of their order.
+
+use std::collections::{HashMap, HashSet};
+
+/// Counts how often every char occurs in a string.
+fn char_counts(s: &str) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Computes the overlap of the char multisets of two strings, regardless of the order of the chars. \
+/// The similarity is `2 * shared / (len_a + len_b)`, where `shared` counts every char as often
+/// as it occurs in both strings. This catches transpositions and reorderings, that Levenshtein penalizes.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 means both strings are anagrams
+/// and 0.0 means they share no chars. Two empty strings are considered identical.
+pub fn bag_similarity(a: &str, b: &str) -> f64 {
+    let counts_a = char_counts(a);
+    let counts_b = char_counts(b);
+
+    let total = counts_a.values().sum::<usize>() + counts_b.values().sum::<usize>();
+    if total == 0 {
+        return 1.;
+    }
+
+    let shared: usize = counts_a
+        .iter()
+        .map(|(c, count)| (*count).min(counts_b.get(c).copied().unwrap_or(0)))
+        .sum();
+    2. * shared as f64 / total as f64
+}
+
+/// Collects the distinct n-grams of a string, or the whole string as a single gram if it is shorter than `n`.
+fn ngrams(chars: &[char], n: usize) -> HashSet<&[char]> {
+    if chars.len() < n {
+        HashSet::from([chars])
+    } else {
+        chars.windows(n).collect()
+    }
+}
+
+/// Computes the Jaccard index of the sets of char n-grams of two strings. \
+/// As only the n-grams are compared, reordered words of longer texts are still considered similar. \
+/// A string shorter than `n` forms a single gram of its whole content, so it only matches an identical short string.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+/// * `n` - The length of the n-grams, where 0 is treated as 1.
+///
+/// # Returns
+///
+/// Returns a `f64` representing `|intersection| / |union|` of the n-gram sets,
+/// where 1.0 means both strings share all n-grams and 0.0 means they share none.
+pub fn ngram_jaccard_similarity(a: &str, b: &str, n: usize) -> f64 {
+    let n = n.max(1);
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let grams_a = ngrams(&a, n);
+    let grams_b = ngrams(&b, n);
+
+    let intersection = grams_a.intersection(&grams_b).count();
+    let union = grams_a.len() + grams_b.len() - intersection;
+    intersection as f64 / union as f64
+}
+
+/// Computes the [`ngram_jaccard_similarity`] of two strings using trigrams.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn trigram_similarity(a: &str, b: &str) -> f64 {
+    ngram_jaccard_similarity(a, b, 3)
+}
+
+/// Computes the Sørensen–Dice coefficient of the adjacent char bigrams of two strings. \
+/// The coefficient is `2 * shared / (bigrams_a + bigrams_b)`, where `shared` counts every bigram as often
+/// as it occurs in both strings. If both strings have fewer than two chars, they are compared for equality.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn dice_coefficient(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len() < 2 && b.len() < 2 {
+        return if a == b { 1. } else { 0. };
+    }
+
+    let mut counts: HashMap<&[char], usize> = HashMap::new();
+    for bigram in a.windows(2) {
+        *counts.entry(bigram).or_insert(0) += 1;
+    }
+    let mut shared = 0;
+    for bigram in b.windows(2) {
+        if let Some(count) = counts.get_mut(bigram).filter(|count| **count > 0) {
+            *count -= 1;
+            shared += 1;
+        }
+    }
+
+    let total = a.len().saturating_sub(1) + b.len().saturating_sub(1);
+    2. * shared as f64 / total as f64
+}
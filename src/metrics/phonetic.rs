@@ -0,0 +1,116 @@
+//! This module defines phonetic functions and a name similarity metric,
+//! which falls back to a phonetic match if the spelling differs too much.
+
+use crate::levenshtein::base::normalized_levenshtein_similarity;
+
+/// The text similarity below which [`name_similarity`] considers a phonetic match.
+pub const DEFAULT_PHONETIC_THRESHOLD: f64 = 0.8;
+
+/// The bonus [`name_similarity`] adds to the text similarity for a phonetic match.
+pub const DEFAULT_PHONETIC_BONUS: f64 = 0.2;
+
+fn soundex_digit(c: char) -> Option<char> {
+    match c {
+        'b' | 'f' | 'p' | 'v' => Some('1'),
+        'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+        'd' | 't' => Some('3'),
+        'l' => Some('4'),
+        'm' | 'n' => Some('5'),
+        'r' => Some('6'),
+        _ => None,
+    }
+}
+
+/// Computes the American Soundex code of a word, such as `"R163"` for `"Robert"`.
+/// All chars that aren't ASCII letters are ignored.
+///
+/// # Arguments
+///
+/// * `word` - The word to encode.
+///
+/// # Returns
+///
+/// Returns the four char Soundex code, or an empty string if the word contains no ASCII letters.
+pub fn soundex(word: &str) -> String {
+    let mut letters = word
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_lowercase());
+
+    let first = match letters.next() {
+        Some(first) => first,
+        None => return String::new(),
+    };
+
+    let mut code = first.to_ascii_uppercase().to_string();
+    let mut last_digit = soundex_digit(first);
+
+    for c in letters {
+        if code.len() == 4 {
+            break;
+        }
+        match soundex_digit(c) {
+            Some(digit) if Some(digit) != last_digit => {
+                code.push(digit);
+                last_digit = Some(digit);
+            }
+            Some(_) => {}
+            // 'h' and 'w' don't separate equal digits, vowels do.
+            None if c == 'h' || c == 'w' => {}
+            None => last_digit = None,
+        }
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+/// Computes the similarity of two names, using [`name_similarity_with`] with the
+/// [`DEFAULT_PHONETIC_THRESHOLD`] and [`DEFAULT_PHONETIC_BONUS`].
+///
+/// # Arguments
+///
+/// * `value` - The name to compare against.
+/// * `query` - The queried name.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn name_similarity(value: &str, query: &str) -> f64 {
+    name_similarity_with(
+        value,
+        query,
+        DEFAULT_PHONETIC_THRESHOLD,
+        DEFAULT_PHONETIC_BONUS,
+    )
+}
+
+/// Computes the case-insensitive Levenshtein similarity of two names.
+/// If it is below `threshold` and both names have the same [`soundex`] code, `bonus` is added to it.
+///
+/// # Arguments
+///
+/// * `value` - The name to compare against.
+/// * `query` - The queried name.
+/// * `threshold` - The text similarity below which a phonetic match is considered.
+/// * `bonus` - The bonus added for a phonetic match.
+///
+/// # Returns
+///
+/// Returns a `f64` representing the similarity ratio, where 1.0 is identical and 0.0 is completely dissimilar.
+pub fn name_similarity_with(value: &str, query: &str, threshold: f64, bonus: f64) -> f64 {
+    let similarity =
+        normalized_levenshtein_similarity(&value.to_lowercase(), &query.to_lowercase());
+    if similarity >= threshold {
+        return similarity;
+    }
+
+    let code = soundex(value);
+    if !code.is_empty() && code == soundex(query) {
+        (similarity + bonus).min(1.)
+    } else {
+        similarity
+    }
+}
@@ -1,12 +1,107 @@
 //! This module provides a generic [`SearchEngine`] struct for building a search engine using the builder pattern.
 
+use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
-use crate::similarity::{Similarity, StatefulCombination, StatelessCombination};
+use crate::similarity::{
+    EmptyQuery, FieldCombination, FieldNormalization, LengthNormalization, QueryStateCombination,
+    Similarity, StatefulCombination, StatelessCombination,
+};
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
+/// A function accessing a searchable text field of a value. \
+/// It is reference counted, so closures capturing state can be used as well, e.g. `Arc::new(|book| book.title.as_str())`.
+pub type FieldAccessor<Value> = Arc<dyn Fn(&Value) -> &str + Send + Sync>;
+
+/// Queries that can be empty, for skipping the similarity functions with
+/// [with_empty_query_score](SearchEngine::with_empty_query_score). \
+/// It is implemented for strings and slices, and can be implemented for custom query types,
+/// e.g. a struct of several search fields, which is empty if all of its fields are.
+pub trait IsEmpty {
+    /// Returns whether the query is empty, in which case every value gets the same score without being compared.
+    fn is_empty(&self) -> bool;
+}
+
+/// A string is empty, if it has no chars. Whitespace isn't trimmed, so `" "` is not empty.
+impl IsEmpty for str {
+    fn is_empty(&self) -> bool {
+        str::is_empty(self)
+    }
+}
+
+/// A slice, e.g. of query tokens, is empty if it has no elements.
+impl<T> IsEmpty for [T] {
+    fn is_empty(&self) -> bool {
+        <[T]>::is_empty(self)
+    }
+}
+
+/// Like [str], a [String] is empty if it has no chars.
+impl IsEmpty for String {
+    fn is_empty(&self) -> bool {
+        String::is_empty(self)
+    }
+}
+
+/// Like a slice, a [Vec] is empty if it has no elements.
+impl<T> IsEmpty for Vec<T> {
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
+}
+
+/// How the weighted similarity of a layer is combined with the similarity of the layers added before it. \
+/// Every layer is folded into the previous ones with its own mode, so [Max](CombineMode::Max) is the default
+/// and layers added with [with_weight_using](SearchEngine::with_weight_using) can choose another one.
+/// The first layer is taken as is, except for [Max](CombineMode::Max), which never returns less than 0.0. \
+/// A NaN similarity of any layer makes the combined similarity NaN in every mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CombineMode {
+    /// The highest similarity of all layers.
+    #[default]
+    Max,
+    /// The sum of the similarities.
+    Sum,
+    /// The arithmetic mean of the similarities. \
+    /// The combined similarity of the previous layers stands in for each of them, so this is only the mean
+    /// of all layers if every layer after the first uses `Mean`. After a [Max](CombineMode::Max) layer,
+    /// the maximum is counted once for every previous layer.
+    Mean,
+    /// The lowest similarity of all layers, so every layer has to match.
+    Min,
+    /// The product of the similarities.
+    Product,
+}
+
+impl CombineMode {
+    /// Combines the similarity of a layer with the combined similarity of the layers before it.
+    ///
+    /// # Arguments
+    ///
+    /// * `combined` - The combined similarity of the previous layers.
+    /// * `similarity` - The weighted similarity of the layer.
+    /// * `layers` - The number of previous layers.
+    pub(crate) fn combine(self, combined: f64, similarity: f64, layers: usize) -> f64 {
+        // Max and Min would otherwise silently drop NaN, hiding it from the threshold and top n filters.
+        if combined.is_nan() || similarity.is_nan() {
+            return f64::NAN;
+        }
+        match self {
+            CombineMode::Max => combined.max(similarity),
+            _ if layers == 0 => similarity,
+            CombineMode::Sum => combined + similarity,
+            CombineMode::Mean => (combined * layers as f64 + similarity) / (layers + 1) as f64,
+            CombineMode::Min => combined.min(similarity),
+            CombineMode::Product => combined * similarity,
+        }
+    }
+}
+
 /// Marker trait for search engine mutability.
 /// Only implemented by [Mutable] and [Immutable].
 /// This Trait is used internally to allow a stateless engine being used immutably.
@@ -48,12 +143,21 @@ impl Mutability for Immutable {}
 ///
 /// If a search engine contains no stateful function, the search and similarity functions can be used immutably.
 ///
+/// Results are sorted with the most similar value first.
+/// [similarities_ascending](SearchEngine::similarities_ascending) and [search_ascending](SearchEngine::search_ascending)
+/// return the least similar value first instead.
+///
+/// When in doubt, [normalized_levenshtein_similarity](crate::levenshtein::base::normalized_levenshtein_similarity)
+/// is the recommended default similarity function, as it is symmetric in its arguments.
+///
 pub struct SearchEngine<Value, Query: ?Sized, S, M: Mutability>
 where
     S: Similarity<Value, Query>,
 {
     values: Vec<(S::State, Value)>,
     similarity: S,
+    limit: Option<usize>,
+    sorted_unique: bool,
     phantom: PhantomData<(M, Query)>,
 }
 
@@ -64,11 +168,85 @@ impl<Value, Query: ?Sized> SearchEngine<Value, Query, (), Immutable> {
         SearchEngine {
             values: Vec::new(),
             similarity: (),
+            limit: None,
+            sorted_unique: false,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Creates a new `SearchEngine` with no values and no similarity functions,
+    /// preallocating space for at least `capacity` values.\
+    /// The similarity defaults to just being 0.0 for all values.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of values to preallocate space for.
+    pub fn with_capacity(capacity: usize) -> SearchEngine<Value, Query, (), Immutable> {
+        SearchEngine {
+            values: Vec::with_capacity(capacity),
+            similarity: (),
+            limit: None,
+            sorted_unique: false,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Creates a new `SearchEngine` from the values of an iterator, without any similarity functions.\
+    /// The values are consumed lazily, without first collecting them into an intermediate vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - An iterator yielding the values of the search engine.
+    pub fn from_iter_values<I>(values: I) -> SearchEngine<Value, Query, (), Immutable>
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        SearchEngine::new().with_values_iter(values)
+    }
+
+    /// Creates a new `SearchEngine` from values that are already sorted and free of duplicates,
+    /// enabling a binary search in [lookup_exact](SearchEngine::lookup_exact). \
+    /// The invariant is only checked in debug builds, in release builds the caller is trusted.
+    /// Adding values later discards the invariant.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The strictly ascending values.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if the values are not strictly ascending.
+    pub fn from_sorted_unique(values: Vec<Value>) -> SearchEngine<Value, Query, (), Immutable>
+    where
+        Value: Ord,
+    {
+        debug_assert!(
+            values.windows(2).all(|pair| pair[0] < pair[1]),
+            "values must be sorted and unique"
+        );
+        SearchEngine {
+            values: values.into_iter().map(|value| ((), value)).collect(),
+            similarity: (),
+            limit: None,
+            sorted_unique: true,
             phantom: Default::default(),
         }
     }
 }
 
+impl<Value, Query: ?Sized> Default for SearchEngine<Value, Query, (), Immutable> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collects values into a search engine without similarity functions, which can be added with the builder pattern.
+impl<Value, Query: ?Sized> FromIterator<Value> for SearchEngine<Value, Query, (), Immutable> {
+    fn from_iter<I: IntoIterator<Item = Value>>(values: I) -> Self {
+        Self::from_iter_values(values)
+    }
+}
+
 impl<Value, Query: ?Sized, S, M: Mutability> SearchEngine<Value, Query, S, M>
 where
     S: Similarity<Value, Query>,
@@ -80,6 +258,8 @@ where
     /// * `value` - The value to be added to the search engine.
     pub fn add_value(&mut self, value: Value) {
         self.values.push((self.similarity.state(&value), value));
+        self.sorted_unique = false;
+        self.refresh_corpus();
     }
 
     /// Adds multiple values to the search engine.
@@ -93,6 +273,169 @@ where
             .map(|v| (self.similarity.state(&v), v))
             .collect();
         self.values.extend(values);
+        self.sorted_unique = false;
+        self.refresh_corpus();
+    }
+
+    /// Adds values from an iterator to the search engine, building the state of every value as it is consumed,
+    /// e.g. while streaming the values from a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - An iterator yielding the values to be added to the search engine.
+    pub fn add_values_iter<I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        let similarity = &self.similarity;
+        self.values
+            .extend(values.into_iter().map(|v| (similarity.state(&v), v)));
+        self.sorted_unique = false;
+        self.refresh_corpus();
+    }
+
+    /// Replaces the value at `index` and recreates its state, keeping the indices of all values stable.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the value to replace, in insertion order.
+    /// * `value` - The new value.
+    ///
+    /// # Returns
+    ///
+    /// Returns the old value, or `None` if `index` is out of bounds, in which case nothing is changed.
+    pub fn update_value(&mut self, index: usize, value: Value) -> Option<Value> {
+        let entry = self.values.get_mut(index)?;
+        let state = self.similarity.state(&value);
+        let (_, old) = std::mem::replace(entry, (state, value));
+        self.sorted_unique = false;
+        self.refresh_corpus();
+        Some(old)
+    }
+
+    /// Removes all values matching the predicate from the search engine, together with their states.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function returning `true` for every value to be removed.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of the removed values, in insertion order.
+    pub fn drain_filter<F>(&mut self, mut predicate: F) -> Vec<Value>
+    where
+        F: FnMut(&Value) -> bool,
+    {
+        let (removed, kept) = std::mem::take(&mut self.values)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(_, value)| predicate(value));
+        self.values = kept;
+        self.refresh_corpus();
+        removed.into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// Removes the value at `index` together with its state.
+    /// The indices of all following values shift down by one.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the value to remove, in insertion order.
+    ///
+    /// # Returns
+    ///
+    /// Returns the removed value, or `None` if `index` is out of bounds, in which case nothing is changed.
+    pub fn remove_value(&mut self, index: usize) -> Option<Value> {
+        if index >= self.values.len() {
+            return None;
+        }
+        let (_, value) = self.values.remove(index);
+        self.refresh_corpus();
+        Some(value)
+    }
+
+    /// Keeps only the values accepted by the predicate, dropping the states of all others.
+    /// Unlike [drain_filter](SearchEngine::drain_filter), the removed values are dropped as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function returning `true` for every value to be kept.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&Value) -> bool,
+    {
+        self.values.retain(|(_, value)| predicate(value));
+        self.refresh_corpus();
+    }
+
+    /// Removes all values and their states from the search engine, keeping its similarity functions.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.refresh_corpus();
+    }
+
+    /// Discards all query-dependent state of the search engine, by recreating the state of every value,
+    /// and recomputes the statistics derived from all values, like the score ranges of
+    /// [with_field_normalization](SearchEngine::with_field_normalization)
+    /// or the document frequencies of [with_idf_boost](SearchEngine::with_idf_boost). \
+    /// This is the escape hatch if external factors, that the state or similarity functions depend on, have changed.
+    /// The next search then starts from scratch, as if the values were just added.
+    ///
+    /// The mutators of the search engine, such as [add_value](SearchEngine::add_value) or
+    /// [drain_filter](SearchEngine::drain_filter), recompute the statistics derived from all values automatically,
+    /// while the states of the values they don't touch stay valid.
+    pub fn invalidate(&mut self) {
+        let similarity = &self.similarity;
+        for (state, value) in self.values.iter_mut() {
+            *state = similarity.state(value);
+        }
+        self.refresh_corpus();
+    }
+
+    /// Recomputes the statistics the similarity functions derive from all values.
+    /// This is shared by all mutators, and does nothing if no layer depends on the values as a whole.
+    fn refresh_corpus(&mut self) {
+        if !self.similarity.uses_corpus() {
+            return;
+        }
+        let values: Vec<&Value> = self.values.iter().map(|(_, value)| value).collect();
+        self.similarity.refresh(&values);
+    }
+
+    /// Changes the weight of a similarity function after the search engine has been built. \
+    /// The similarity functions are numbered in the order they were added, starting at 0.
+    /// As the weights don't affect the states, these are kept as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The index of the similarity function.
+    /// * `weight` - The new weight of the similarity function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` is not smaller than the number of similarity functions.
+    pub fn set_weight(&mut self, layer: usize, weight: f64) {
+        let layers = self.similarity.layers();
+        match self.similarity.weight_mut(layer) {
+            Some(w) => *w = weight,
+            None => panic!("layer {layer} out of bounds for {layers} similarity functions"),
+        }
+    }
+
+    /// Limits the number of results of the sorting search and similarity functions
+    /// to the `limit` most similar values with the builder pattern. \
+    /// The order of the results is unchanged, but values with a NaN score are excluded.
+    /// The explicit [similarities_top_k](SearchEngine::similarities_top_k),
+    /// [similarities_by](SearchEngine::similarities_by) and
+    /// [unsorted_similarities](SearchEngine::unsorted_similarities) functions are not affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of results.
+    pub fn with_limit(self, limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..self
+        }
     }
 
     /// Adds a single value to the search engine with the builder pattern.
@@ -101,12 +444,8 @@ where
     ///
     /// * `value` - The value to be added to the search engine.
     pub fn with_value(mut self, value: Value) -> Self {
-        self.values.push((self.similarity.state(&value), value));
-        Self {
-            values: self.values,
-            similarity: self.similarity,
-            phantom: Default::default(),
-        }
+        self.add_value(value);
+        self
     }
 
     /// Adds multiple values to the search engine with the builder pattern.
@@ -115,20 +454,29 @@ where
     ///
     /// * `values` - A vector of values to be added to the search engine.
     pub fn with_values(mut self, values: Vec<Value>) -> Self {
-        let values: Vec<_> = values
-            .into_iter()
-            .map(|v| (self.similarity.state(&v), v))
-            .collect();
-        self.values.extend(values);
-        Self {
-            values: self.values,
-            similarity: self.similarity,
-            phantom: Default::default(),
-        }
+        self.add_values(values);
+        self
+    }
+
+    /// Adds values from an iterator to the search engine with the builder pattern. \
+    /// The values are consumed lazily and their states are built as they are added,
+    /// without first collecting them into an intermediate vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - An iterator yielding the values to be added to the search engine.
+    pub fn with_values_iter<I>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        self.add_values_iter(values);
+        self
     }
 
     /// Adds a key function to use for determining the similarity of a value to the query.
-    /// This is identical to `with_weight` with a weight of 1.0.
+    /// This is identical to `with_weight` with a weight of 1.0. \
+    /// [normalized_levenshtein_similarity](crate::levenshtein::base::normalized_levenshtein_similarity)
+    /// is a good default for string values.
     ///
     /// # Arguments
     ///
@@ -161,10 +509,183 @@ where
         SearchEngine {
             values: self.values,
             similarity,
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Adds a weighted function, which is combined with the previously added functions using the given mode
+    /// instead of the maximum, e.g. [Min](CombineMode::Min) to require every field to match.
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - The weight of the similarity function.
+    /// * `mode` - How the similarity is combined with the similarities of the previous functions.
+    /// * `function` - A function for determining the similarity between a value and the query.
+    pub fn with_weight_using<Func>(
+        self,
+        weight: f64,
+        mode: CombineMode,
+        function: Func,
+    ) -> SearchEngine<Value, Query, StatelessCombination<Value, Query, S, Func>, M>
+    where
+        Func: Fn(&Value, &Query) -> f64,
+    {
+        let similarity = self.similarity.with_weight_using(weight, mode, function);
+        SearchEngine {
+            values: self.values,
+            similarity,
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Adds a weighted function with a label, which names its layer in
+    /// [explain](SearchEngine::explain) instead of an anonymous layer index.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The name of the similarity function, e.g. the field it compares.
+    /// * `weight` - The weight of the similarity function.
+    /// * `function` - A function for determining the similarity between a value and the query.
+    pub fn with_labeled_weight<Func>(
+        self,
+        label: &str,
+        weight: f64,
+        function: Func,
+    ) -> SearchEngine<Value, Query, StatelessCombination<Value, Query, S, Func>, M>
+    where
+        Func: Fn(&Value, &Query) -> f64,
+    {
+        let similarity = self.similarity.with_labeled_weight(label, weight, function);
+        SearchEngine {
+            values: self.values,
+            similarity,
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Adds a distance function to use for determining the similarity of a value to the query,
+    /// where a smaller distance means a more similar value. \
+    /// A distance `d` is converted into the similarity `1 / (1 + d)`, so a distance of 0.0 results in a similarity of 1.0
+    /// and growing distances approach a similarity of 0.0. This keeps the order of the distances,
+    /// while allowing the function to be combined with similarity functions.
+    /// Negative distances are not supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `function` - A function for determining the distance between a value and the query.
+    #[allow(clippy::type_complexity)]
+    pub fn with_distance<Func>(
+        self,
+        function: Func,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatelessCombination<Value, Query, S, impl Fn(&Value, &Query) -> f64>,
+        M,
+    >
+    where
+        Func: Fn(&Value, &Query) -> f64,
+    {
+        self.with(move |value: &Value, query: &Query| 1. / (1. + function(value, query)))
+    }
+
+    /// Adds a layer for every field of a value, determining the similarity of the field to the query.
+    /// This is the equivalent of calling [with_labeled_weight](SearchEngine::with_labeled_weight) once for every field,
+    /// but allows the fields and their weights to be determined at runtime, e.g. from a config file. \
+    /// Every layer is labeled with the name of its field and combined with the maximum,
+    /// so the weights can be changed with [set_weight](SearchEngine::set_weight) like those of any other layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - The name, weight and accessor of every field.
+    /// * `metric` - A function for determining the similarity between a field and the query.
+    pub fn with_fields_weighted<Metric>(
+        self,
+        fields: Vec<(&str, f64, FieldAccessor<Value>)>,
+        metric: Metric,
+    ) -> SearchEngine<Value, Query, FieldCombination<Value, Query, S, Metric>, M>
+    where
+        Metric: Fn(&str, &Query) -> f64,
+    {
+        SearchEngine {
+            values: self.values,
+            similarity: self
+                .similarity
+                .with_fields(fields, CombineMode::Max, metric),
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
             phantom: Default::default(),
         }
     }
 
+    /// Adds a layer for every field of a value like [with_fields_weighted](SearchEngine::with_fields_weighted),
+    /// looking up the weight of every field by its name. \
+    /// Fields without an entry in `weights` don't get a layer, so leaving a field out of the map disables it.
+    /// Entries of `weights` not naming any field are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - The weight of every field by its name.
+    /// * `fields` - The name and accessor of every field.
+    /// * `metric` - A function for determining the similarity between a field and the query.
+    pub fn with_field_weights<Metric>(
+        self,
+        weights: &HashMap<String, f64>,
+        fields: Vec<(&str, FieldAccessor<Value>)>,
+        metric: Metric,
+    ) -> SearchEngine<Value, Query, FieldCombination<Value, Query, S, Metric>, M>
+    where
+        Metric: Fn(&str, &Query) -> f64,
+    {
+        let fields = fields
+            .into_iter()
+            .filter_map(|(name, accessor)| {
+                weights.get(name).map(|weight| (name, *weight, accessor))
+            })
+            .collect();
+        self.with_fields_weighted(fields, metric)
+    }
+
+    /// Adds a layer for every field of a value, determining the similarity of the field to the query,
+    /// where the score of every field is rescaled to the range of scores the field typically has. \
+    /// Longer fields, like descriptions, systematically result in lower similarities than short ones, like titles,
+    /// which lets the short fields dominate the maximum. To counter this, every field of every value is scored against
+    /// the sample queries, and its scores are min-max normalized with the lowest and highest score found,
+    /// clamped to the range from 0.0 to 1.0. \
+    /// The ranges are computed from the values present when this function is called,
+    /// and recomputed whenever values are added or removed, or the search engine is [invalidated](SearchEngine::invalidate).
+    /// A field, whose scores don't vary across the values, is scored without normalization. \
+    /// Like with [with_fields_weighted](SearchEngine::with_fields_weighted), every layer is labeled with the name of its field,
+    /// so the weights can be changed with [set_weight](SearchEngine::set_weight) like those of any other layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - The name, weight and accessor of every field.
+    /// * `mode` - How the normalized fields are combined with the previous layers.
+    /// * `metric` - A function for determining the similarity between a field and the query.
+    /// * `queries` - Representative queries to determine the score range of every field.
+    pub fn with_field_normalization<Metric>(
+        self,
+        fields: Vec<(&str, f64, FieldAccessor<Value>)>,
+        mode: CombineMode,
+        metric: Metric,
+        queries: &[&Query],
+    ) -> SearchEngine<Value, Query, FieldNormalization<Value, Query, S, Metric>, M>
+    where
+        Metric: Fn(&str, &Query) -> f64,
+        Query: ToOwned,
+    {
+        self.with_layer(|similarity| {
+            similarity.with_field_normalization(fields, mode, metric, queries)
+        })
+    }
+
     /// Adds a stateful function to use for determining the similarity of a value to the query.
     /// This is identical to `with_state_and_weight` with a weight of 1.0.
     ///
@@ -172,6 +693,7 @@ where
     ///
     /// * `state_function` - A function for creating the state for a value.
     /// * `function` - A function for determining the similarity between a value and the query.
+    #[allow(clippy::type_complexity)]
     pub fn with_state<Func, StateFunc, State>(
         self,
         state_func: StateFunc,
@@ -189,6 +711,35 @@ where
         self.with_state_and_weight(1., state_func, function)
     }
 
+    /// Adds a function scoring a precomputed representation of a value, e.g. its token set,
+    /// instead of the value itself. \
+    /// The representation is computed once per value when it is added and kept as its state,
+    /// which makes the search engine stateful, but the scoring function only gets shared access to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `precompute` - A function for computing the representation of a value.
+    /// * `score` - A function for determining the similarity between a representation and the query.
+    #[allow(clippy::type_complexity)]
+    pub fn with_precomputed<P, PFunc, Score>(
+        self,
+        precompute: PFunc,
+        score: Score,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatefulCombination<Value, Query, S, impl Fn(&mut P, &Value, &Query) -> f64, PFunc, P>,
+        Mutable,
+    >
+    where
+        PFunc: Fn(&Value) -> P,
+        Score: Fn(&P, &Query) -> f64,
+    {
+        self.with_state(precompute, move |p: &mut P, _: &Value, query: &Query| {
+            score(p, query)
+        })
+    }
+
     /// Adds a stateful and weighted function to use for determining the similarity of a value to the query.
     ///
     /// # Arguments
@@ -196,6 +747,7 @@ where
     /// * `weight` - The weight of the similarity function.
     /// * `state_function` - A function for creating the state for a value.
     /// * `function` - A function for determining the similarity between a value and the query.
+    #[allow(clippy::type_complexity)]
     pub fn with_state_and_weight<Func, StateFunc, State>(
         self,
         weight: f64,
@@ -222,36 +774,272 @@ where
         SearchEngine {
             values,
             similarity,
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
             phantom: Default::default(),
         }
     }
 
-    /// Retrieves a sorted vector of tuples containing the values and their similarity scores
-    /// to the given query.
+    /// Adds a stateful and weighted function, which is combined with the previously added functions
+    /// using the given mode instead of the maximum.
     ///
     /// # Arguments
     ///
-    /// * `query` - The query against which to rank the values.
-    ///
-    /// # Returns
-    ///
-    /// Returns a vector of tuples where the first element is a reference to a value and the second element
-    /// is its similarity score as a floating-point number.
-    pub fn into_similarities(self, query: &Query) -> Vec<(Value, f64)> {
-        let mut values = self
-            .values
-            .into_iter()
-            .map(|(mut state, value)| {
-                let similarity = self.similarity.similarity(&mut state, &value, query);
+    /// * `weight` - The weight of the similarity function.
+    /// * `mode` - How the similarity is combined with the similarities of the previous functions.
+    /// * `state_function` - A function for creating the state for a value.
+    /// * `function` - A function for determining the similarity between a value and the query.
+    #[allow(clippy::type_complexity)]
+    pub fn with_state_using<Func, StateFunc, State>(
+        self,
+        weight: f64,
+        mode: CombineMode,
+        state_function: StateFunc,
+        function: Func,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatefulCombination<Value, Query, S, Func, StateFunc, State>,
+        Mutable,
+    >
+    where
+        Func: Fn(&mut State, &Value, &Query) -> f64,
+        StateFunc: Fn(&Value) -> State,
+    {
+        let similarity = self
+            .similarity
+            .with_state_using(weight, mode, function, state_function);
+        let values: Vec<_> = self
+            .values
+            .into_iter()
+            .map(|(_, value)| (similarity.state(&value), value))
+            .collect();
+        SearchEngine {
+            values,
+            similarity,
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Adds a stateful and weighted function with a label, which names its layer in
+    /// [explain](SearchEngine::explain) instead of an anonymous layer index.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The name of the similarity function, e.g. the field it compares.
+    /// * `weight` - The weight of the similarity function.
+    /// * `state_function` - A function for creating the state for a value.
+    /// * `function` - A function for determining the similarity between a value and the query.
+    #[allow(clippy::type_complexity)]
+    pub fn with_labeled_state<Func, StateFunc, State>(
+        self,
+        label: &str,
+        weight: f64,
+        state_function: StateFunc,
+        function: Func,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatefulCombination<Value, Query, S, Func, StateFunc, State>,
+        Mutable,
+    >
+    where
+        Func: Fn(&mut State, &Value, &Query) -> f64,
+        StateFunc: Fn(&Value) -> State,
+    {
+        let similarity =
+            self.similarity
+                .with_labeled_state(label, weight, function, state_function);
+        let values: Vec<_> = self
+            .values
+            .into_iter()
+            .map(|(_, value)| (similarity.state(&value), value))
+            .collect();
+        SearchEngine {
+            values,
+            similarity,
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Adds a stateful function with a query state to use for determining the similarity of a value to the query.
+    /// This is identical to `with_query_state_and_weight` with a weight of 1.0.
+    ///
+    /// # Arguments
+    ///
+    /// * `state_function` - A function for creating the state for a value.
+    /// * `query_function` - A function for preprocessing the query.
+    /// * `function` - A function for determining the similarity between a value and the preprocessed query.
+    #[allow(clippy::type_complexity)]
+    pub fn with_query_state<Func, StateFunc, State, QueryFunc, QueryState>(
+        self,
+        state_function: StateFunc,
+        query_function: QueryFunc,
+        function: Func,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        QueryStateCombination<Value, Query, S, Func, StateFunc, State, QueryFunc, QueryState>,
+        Mutable,
+    >
+    where
+        Func: Fn(&mut State, &QueryState, &Value) -> f64,
+        StateFunc: Fn(&Value) -> State,
+        QueryFunc: Fn(&Query) -> QueryState,
+    {
+        self.with_query_state_and_weight(1., state_function, query_function, function)
+    }
+
+    /// Adds a stateful and weighted function with a query state to use for determining the similarity of a value to the query.
+    ///
+    /// The query state is created by `query_function` once per search and shared by all values,
+    /// which is useful if the query requires expensive preprocessing, such as tokenization. \
+    /// The value state on the other hand is created once per value and persists between searches,
+    /// just like the state of [with_state_and_weight](SearchEngine::with_state_and_weight).
+    /// This means an incremental value state, such as
+    /// [IncrementalLevenshtein](crate::levenshtein::incremental::IncrementalLevenshtein),
+    /// still sees every search in order, while the query state is always fresh.
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - The weight of the similarity function.
+    /// * `state_function` - A function for creating the state for a value.
+    /// * `query_function` - A function for preprocessing the query.
+    /// * `function` - A function for determining the similarity between a value and the preprocessed query.
+    #[allow(clippy::type_complexity)]
+    pub fn with_query_state_and_weight<Func, StateFunc, State, QueryFunc, QueryState>(
+        self,
+        weight: f64,
+        state_function: StateFunc,
+        query_function: QueryFunc,
+        function: Func,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        QueryStateCombination<Value, Query, S, Func, StateFunc, State, QueryFunc, QueryState>,
+        Mutable,
+    >
+    where
+        Func: Fn(&mut State, &QueryState, &Value) -> f64,
+        StateFunc: Fn(&Value) -> State,
+        QueryFunc: Fn(&Query) -> QueryState,
+    {
+        let similarity = self.similarity.with_query_state_and_weight(
+            weight,
+            function,
+            state_function,
+            query_function,
+        );
+        let values: Vec<_> = self
+            .values
+            .into_iter()
+            .map(|(_, value)| (similarity.state(&value), value))
+            .collect();
+        SearchEngine {
+            values,
+            similarity,
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Boosts the similarity of short values, so that e.g. "cat" outranks "category" for the query "cat",
+    /// even if both reach the same similarity. \
+    /// The similarity of a value with `n` chars is multiplied with `1 + strength / n`,
+    /// so scores can exceed 1.0. The boost is computed once per value and kept in its state,
+    /// which makes the search engine stateful. \
+    /// It applies to the combined similarity of all functions added so far, after their weights,
+    /// so it scales every layer alike and doesn't change their relative weights.
+    /// Functions added afterwards are not boosted.
+    ///
+    /// # Arguments
+    ///
+    /// * `strength` - How strongly short values are preferred, where 0.0 disables the boost.
+    pub fn with_length_normalization(
+        self,
+        strength: f64,
+    ) -> SearchEngine<Value, Query, LengthNormalization<Value, Query, S>, Mutable>
+    where
+        Value: AsRef<str>,
+    {
+        let similarity = self.similarity.with_length_normalization(strength);
+        let values: Vec<_> = self
+            .values
+            .into_iter()
+            .map(|(_, value)| (similarity.state(&value), value))
+            .collect();
+        SearchEngine {
+            values,
+            similarity,
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Skips the similarity functions for an empty query and gives every value the same `score` instead. \
+    /// Without this, an empty query is scored like any other query, which runs every similarity function on every value.
+    /// As equal scores keep their relative order, an empty query then returns all values in insertion order,
+    /// e.g. for autocomplete showing everything before the first keystroke. \
+    /// This applies to every kind of search alike, borrowing, consuming and parallel.
+    /// The score is chosen explicitly, as metrics disagree on the similarity of an empty query.
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - The similarity of every value to an empty query.
+    pub fn with_empty_query_score(
+        self,
+        score: f64,
+    ) -> SearchEngine<Value, Query, EmptyQuery<Value, Query, S>, M>
+    where
+        Query: IsEmpty,
+    {
+        SearchEngine {
+            values: self.values,
+            similarity: self.similarity.with_empty_query_score(score),
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Retrieves a sorted vector of tuples containing the values and their similarity scores
+    /// to the given query, with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn into_similarities(self, query: &Query) -> Vec<(Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let limit = self.limit;
+        let values = self
+            .values
+            .into_iter()
+            .map(|(mut state, value)| {
+                let similarity =
+                    self.similarity
+                        .similarity(&mut state, &query_state, &value, query);
                 (value, similarity)
             })
             .collect::<Vec<_>>();
-        values.sort_unstable_by(|(_, v), (_, s)| v.partial_cmp(s).unwrap_or(Ordering::Equal));
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
         values
     }
 
     /// Performs a search based on the given query and returns a vector of the values ranked
-    /// by similarity.
+    /// by similarity, with the most similar value first.
     ///
     /// # Arguments
     ///
@@ -267,97 +1055,1442 @@ where
             .collect()
     }
 
+    /// Consumes the search engine and returns the most similar value to the given query together with its similarity score,
+    /// scoring the values in a single pass without collecting or sorting them. \
+    /// Of multiple equally similar values the one added first is returned, values with a NaN score are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to compare the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns the best match, or `None` if the search engine contains no values.
+    pub fn into_best_match(self, query: &Query) -> Option<(Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let similarity = self.similarity;
+        let best = best(self.values.into_iter().map(|(mut state, value)| {
+            let score = similarity.similarity(&mut state, &query_state, &value, query);
+            (value, score)
+        }));
+        trace.finish(best.iter().count(), scores_of(&best));
+        best
+    }
+
+    /// Splits the search engine into `n` search engines of roughly equal size,
+    /// each with a clone of the similarity functions. \
+    /// The values keep their states and their insertion order, so merging the results of all shards
+    /// yields the same ranking as querying the whole search engine.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of shards to create.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of `n` search engines, whose sizes differ by at most one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn shards(self, n: usize) -> Vec<SearchEngine<Value, Query, S, M>>
+    where
+        S: Clone,
+    {
+        assert!(n != 0, "number of shards must be non-zero");
+
+        let (base, remainder) = (self.values.len() / n, self.values.len() % n);
+        let mut values = self.values.into_iter();
+        (0..n)
+            .map(|i| SearchEngine {
+                values: values
+                    .by_ref()
+                    .take(base + usize::from(i < remainder))
+                    .collect(),
+                similarity: self.similarity.clone(),
+                limit: self.limit,
+                sorted_unique: self.sorted_unique,
+                phantom: Default::default(),
+            })
+            .collect()
+    }
+
+    /// Looks up a value equal to the given key. \
+    /// If the search engine was created by [from_sorted_unique](SearchEngine::from_sorted_unique)
+    /// and no values have been added since, this is a binary search in `O(log n)`,
+    /// otherwise all values are compared in insertion order.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    ///
+    /// # Returns
+    ///
+    /// Returns a reference to the value equal to the key, or `None` if there is no such value.
+    pub fn lookup_exact<K>(&self, key: &K) -> Option<&Value>
+    where
+        Value: Borrow<K>,
+        K: Ord + ?Sized,
+    {
+        if self.sorted_unique {
+            self.values
+                .binary_search_by(|(_, value)| value.borrow().cmp(key))
+                .ok()
+                .map(|index| &self.values[index].1)
+        } else {
+            self.values()
+                .find(|value| (*value).borrow().cmp(key) == Ordering::Equal)
+        }
+    }
+
+    /// Returns all values starting with the given prefix, in insertion order. \
+    /// This is an exact comparison, which doesn't use the similarity functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix the values have to start with.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the matching values.
+    pub fn starts_with(&self, prefix: &str) -> Vec<&Value>
+    where
+        Value: AsRef<str>,
+    {
+        self.values()
+            .filter(|value| value.as_ref().starts_with(prefix))
+            .collect()
+    }
+
+    /// Returns all values ending with the given suffix, in insertion order. \
+    /// This is an exact comparison, which doesn't use the similarity functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `suffix` - The suffix the values have to end with.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the matching values.
+    pub fn ends_with(&self, suffix: &str) -> Vec<&Value>
+    where
+        Value: AsRef<str>,
+    {
+        self.values()
+            .filter(|value| value.as_ref().ends_with(suffix))
+            .collect()
+    }
+
+    /// Returns a reference to the value at `index` in insertion order, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&Value> {
+        self.values.get(index).map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the values of the search engine, in insertion order.
+    pub fn values(&self) -> impl ExactSizeIterator<Item = &Value> + DoubleEndedIterator {
+        self.values.iter().map(|(_, value)| value)
+    }
+
+    /// Returns the number of values in the search engine.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the search engine contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the number of values the search engine can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    pub(crate) fn similarity(&self) -> &S {
+        &self.similarity
+    }
+
+    pub(crate) fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Wraps the similarity functions into a new layer keeping the states of the values,
+    /// and computes the statistics the new layer derives from all values.
+    pub(crate) fn with_layer<T, Layer>(self, layer: Layer) -> SearchEngine<Value, Query, T, M>
+    where
+        T: Similarity<Value, Query, State = S::State>,
+        Layer: FnOnce(S) -> T,
+    {
+        let mut search_engine = SearchEngine {
+            values: self.values,
+            similarity: layer(self.similarity),
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
+            phantom: Default::default(),
+        };
+        search_engine.refresh_corpus();
+        search_engine
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn similarity_and_values_mut(
+        &mut self,
+    ) -> (&S, impl Iterator<Item = (&mut S::State, &Value)>) {
+        (
+            &self.similarity,
+            self.values
+                .iter_mut()
+                .map(|(state, value)| (state, &*value)),
+        )
+    }
+
     #[doc(hidden)]
     pub fn get_values_with_state(&self) -> &[(<S as Similarity<Value, Query>>::State, Value)] {
         &self.values
     }
-}
-impl<Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Mutable>
-where
-    S: Similarity<Value, Query>,
-{
+}
+impl<Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Mutable>
+where
+    S: Similarity<Value, Query>,
+{
+    /// Adds a single value to the search engine and scores it against the current query,
+    /// so its state is as up to date as the states of the values that were already searched. \
+    /// Without this, the state of a new value starts at the empty query
+    /// and has to catch up on the next search.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to be added to the search engine.
+    /// * `current_query` - The query the other states were last updated with.
+    pub fn add_value_seeded(&mut self, value: Value, current_query: &Query) {
+        let mut state = self.similarity.state(&value);
+        let query_state = self.similarity.query_state(current_query);
+        self.similarity
+            .similarity(&mut state, &query_state, &value, current_query);
+        self.values.push((state, value));
+        self.sorted_unique = false;
+        self.refresh_corpus();
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, with the most similar value first. \
+    /// An empty query is scored like any other query, skipping the similarity functions for it is opt-in
+    /// with [with_empty_query_score](SearchEngine::with_empty_query_score). \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let values = sort_limited(self.score_values(query), limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Performs a search based on the given query and returns a vector of references to the values ranked
+    /// by similarity, with the most similar value first. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search(&mut self, query: &Query) -> Vec<&Value> {
+        self.similarities(query).into_iter().map(|v| v.0).collect()
+    }
+
+    /// Retrieves a sorted vector of tuples like [similarities](SearchEngine::similarities),
+    /// but shuffles every group of values with equal similarity scores. \
+    /// The shuffle only depends on the seed, so the same seed always results in the same order,
+    /// which avoids a position bias among equally good results while staying reproducible. \
+    /// With a [limit](SearchEngine::with_limit), the values tied with the last kept value are shuffled
+    /// before the results are truncated, so any of them may be returned. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `seed` - The seed of the shuffle.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_shuffled_ties(&mut self, query: &Query, seed: u64) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let values = sort_shuffled_ties(self.score_values(query), limit, seed);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, in insertion order and without sorting. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn unsorted_similarities(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let values = self.score_values(query);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Scores every value against the query in insertion order, without tracing the search.
+    fn score_values(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        let query_state = self.similarity.query_state(query);
+        self.values
+            .iter_mut()
+            .map(|(state, value)| {
+                (
+                    value as &Value,
+                    self.similarity
+                        .similarity(state, &query_state, value, query),
+                )
+            })
+            .collect()
+    }
+
+    /// Calculates the similarity score of every value to the given query, in insertion order. \
+    /// The scores are aligned with [values](SearchEngine::values), so `scores(query)[i]` is the score of the `i`-th value.
+    /// Nothing is sorted and no references are collected, which makes this the cheapest way of scoring all values. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to score the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector containing the similarity score of every value.
+    pub fn scores(&mut self, query: &Query) -> Vec<f64> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let scores: Vec<f64> = self
+            .values
+            .iter_mut()
+            .map(|(state, value)| {
+                self.similarity
+                    .similarity(state, &query_state, value, query)
+            })
+            .collect();
+        trace.finish(scores.len(), scores.iter().copied());
+        scores
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the given comparator. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `cmp` - The comparator determining the order of the scored values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_by<F>(&mut self, query: &Query, cmp: F) -> Vec<(&Value, f64)>
+    where
+        F: FnMut(&(&Value, f64), &(&Value, f64)) -> Ordering,
+    {
+        let trace = SearchTrace::start(query, self.values.len());
+        let mut values = self.score_values(query);
+        values.sort_by(cmp);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, only considering the values accepted by the predicate, with the most similar value first. \
+    /// Rejected values are skipped before scoring, so a hard filter can be combined with the fuzzy ranking
+    /// without paying for the similarity functions of values that are discarded anyway. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `predicate` - A function deciding whether a value is considered at all.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn search_filtered<P>(&mut self, query: &Query, predicate: P) -> Vec<(&Value, f64)>
+    where
+        P: Fn(&Value) -> bool,
+    {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let limit = self.limit;
+        let values = self
+            .values
+            .iter_mut()
+            .filter(|(_, value)| predicate(value))
+            .map(|(state, value)| {
+                (
+                    value as &Value,
+                    self.similarity
+                        .similarity(state, &query_state, value, query),
+                )
+            })
+            .collect::<Vec<_>>();
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, keeping only the most similar value of all values sharing a key.
+    /// The result is sorted with the most similar value first. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `key` - A function determining the key of a value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn search_dedup_by<K, F>(&mut self, query: &Query, key: F) -> Vec<(&Value, f64)>
+    where
+        K: Eq + Hash,
+        F: Fn(&Value) -> K,
+    {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let mut values = self.score_values(query);
+        sort_descending(&mut values);
+        let values = dedup_by_key(values, |value| key(value));
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Retrieves the values and their similarity scores as integer percentages, sorted like
+    /// [similarities](SearchEngine::similarities). \
+    /// The scores are clamped to `[0.0, 1.0]`, scaled to `0..=100` and rounded to the nearest integer,
+    /// with halves rounded up. A NaN score results in 0. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score in percent.
+    pub fn match_percentages(&mut self, query: &Query) -> Vec<(&Value, u8)> {
+        self.similarities(query)
+            .into_iter()
+            .map(|(value, similarity)| (value, to_percentage(similarity)))
+            .collect()
+    }
+
+    /// Performs a search based on the given query and groups the values by the tier their similarity score falls into. \
+    /// A value belongs to the highest tier whose boundary its score reaches.
+    /// Values scoring below the lowest boundary are omitted. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    /// * `tiers` - The lower boundaries of the tiers, in any order.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector with one tuple per tier, ordered from the highest to the lowest boundary.
+    /// The first element is the boundary of the tier, the second the values within the tier,
+    /// ranked with the most similar value first. Tiers without values are kept with an empty vector.
+    pub fn grouped_search(&mut self, query: &Query, tiers: &[f64]) -> Vec<(f64, Vec<&Value>)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let mut values = self.score_values(query);
+        sort_descending(&mut values);
+        let top_score = values.first().map(|(_, similarity)| *similarity);
+        let groups = group_by_tiers(values, tiers);
+        let results = groups.iter().map(|(_, group)| group.len()).sum();
+        trace.finish(results, top_score);
+        groups
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the most similar value first. \
+    /// This is identical to [similarities](SearchEngine::similarities), which uses the same order. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_descending(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let values = sort_limited(self.score_values(query), limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Performs a search based on the given query and returns a vector of references to the values ranked
+    /// by similarity, with the most similar value first. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_descending(&mut self, query: &Query) -> Vec<&Value> {
+        self.similarities_descending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the least similar value first. \
+    /// It returns the same values as [similarities](SearchEngine::similarities), in the opposite order. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_ascending(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let values = sort_limited(self.score_values(query), limit, false);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Performs a search based on the given query and returns a vector of references to the values ranked
+    /// by similarity, with the least similar value first. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values in ascending order of their similarity to the query.
+    pub fn search_ascending(&mut self, query: &Query) -> Vec<&Value> {
+        self.similarities_ascending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. Values with a NaN score are excluded. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_top_k(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let values = top_k(self.score_values(query), k);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Performs a search based on the given query and returns references to the `k` most similar values,
+    /// with the most similar value first. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` references to the values ranked by their similarity to the query.
+    pub fn search_top_k(&mut self, query: &Query, k: usize) -> Vec<&Value> {
+        self.similarities_top_k(query, k)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `N` values most similar to the given query together with their similarity scores,
+    /// like [similarities_top_k](SearchEngine::similarities_top_k), but without allocating on the heap. \
+    /// The slots are filled with the most similar value first, followed by `None` if there are fewer than `N` values
+    /// with a score other than NaN. This is meant for small `N`, e.g. the suggestions of an autocomplete. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns an array of `N` slots and the number of filled slots.
+    pub fn top_n<const N: usize>(&mut self, query: &Query) -> ([Option<(&Value, f64)>; N], usize) {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let (slots, filled) = top_n(self.values.iter_mut().map(|(state, value)| {
+            let similarity = self
+                .similarity
+                .similarity(state, &query_state, value, query);
+            (&*value, similarity)
+        }));
+        trace.finish(filled, scores_of(slots.iter().flatten()));
+        (slots, filled)
+    }
+
+    /// Retrieves the `k` most similar values of a random sample, together with their similarity scores,
+    /// sorted with the most similar value first. \
+    /// Only `sample` values are scored, so the cost doesn't depend on the size of the search engine,
+    /// but the results are approximate: a value outside of the sample is never returned,
+    /// even if it is the best match. The larger the sample in relation to all values,
+    /// the more likely the true top results are found. The sample only depends on the seed. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `sample` - The number of randomly chosen values to score.
+    /// * `k` - The maximum number of values to return.
+    /// * `seed` - The seed of the random sample.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn approx_search(
+        &mut self,
+        query: &Query,
+        sample: usize,
+        k: usize,
+        seed: u64,
+    ) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let indices = sample_indices(self.values.len(), sample, seed);
+        let query_state = self.similarity.query_state(query);
+        let mut indices = indices.into_iter().peekable();
+        let values: Vec<_> = self
+            .values
+            .iter_mut()
+            .enumerate()
+            .filter(|(index, _)| indices.next_if_eq(index).is_some())
+            .map(|(_, (state, value))| {
+                (
+                    value as &Value,
+                    self.similarity
+                        .similarity(state, &query_state, value, query),
+                )
+            })
+            .collect();
+        let values = top_k(values, k);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, excluding all values with a score below `threshold` or a NaN score,
+    /// with the most similar value first. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_above(&mut self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let values = above(self.score_values(query), threshold);
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Performs a search based on the given query and returns a vector of references to the values ranked
+    /// by similarity, excluding all values with a score below `threshold` or a NaN score. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_above(&mut self, query: &Query, threshold: f64) -> Vec<&Value> {
+        self.similarities_above(query, threshold)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Returns the most similar value to the given query together with its similarity score,
+    /// scoring the values in a single pass without collecting or sorting them. \
+    /// Of multiple equally similar values the one added first is returned, values with a NaN score are ignored. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to compare the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns the best match, or `None` if the search engine contains no values.
+    pub fn best_match(&mut self, query: &Query) -> Option<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let best = best(self.values.iter_mut().map(|(state, value)| {
+            let similarity = self
+                .similarity
+                .similarity(state, &query_state, value, query);
+            (value as &Value, similarity)
+        }));
+        trace.finish(best.iter().count(), scores_of(&best));
+        best
+    }
+
+    /// Returns the most similar value to the given query together with its similarity score,
+    /// if its score is at least `threshold`. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to compare the values.
+    /// * `threshold` - The minimum similarity score of the returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns the best match, or `None` if no value reaches the threshold.
+    pub fn best_match_above(&mut self, query: &Query, threshold: f64) -> Option<(&Value, f64)> {
+        self.best_match(query)
+            .filter(|(_, similarity)| *similarity >= threshold)
+    }
+
+    /// Counts the values with a similarity score of at least `threshold` to the given query,
+    /// without collecting or sorting them. Values with a NaN score are not counted. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to score the values.
+    /// * `threshold` - The minimum similarity score a value must have to be counted.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of values scoring at least `threshold`.
+    pub fn count_above(&mut self, query: &Query, threshold: f64) -> usize {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let count = self
+            .values
+            .iter_mut()
+            .map(|(state, value)| {
+                self.similarity
+                    .similarity(state, &query_state, value, query)
+            })
+            .filter(|similarity| *similarity >= threshold)
+            .count();
+        trace.finish(count, None);
+        count
+    }
+}
+
+impl<Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Immutable>
+where
+    S: Similarity<Value, Query, State = ()>,
+{
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, with the most similar value first. \
+    /// An empty query is scored like any other query, skipping the similarity functions for it is opt-in
+    /// with [with_empty_query_score](SearchEngine::with_empty_query_score). \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities(&self, query: &Query) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let values = sort_limited(self.score_values(query), self.limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Performs a search based on the given query and returns a vector of references to the values ranked
+    /// by similarity, with the most similar value first. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search(&self, query: &Query) -> Vec<&Value> {
+        self.similarities(query).into_iter().map(|v| v.0).collect()
+    }
+
+    /// Retrieves a sorted vector of tuples like [similarities](SearchEngine::similarities),
+    /// but shuffles every group of values with equal similarity scores. \
+    /// The shuffle only depends on the seed, so the same seed always results in the same order,
+    /// which avoids a position bias among equally good results while staying reproducible. \
+    /// With a [limit](SearchEngine::with_limit), the values tied with the last kept value are shuffled
+    /// before the results are truncated, so any of them may be returned. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `seed` - The seed of the shuffle.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_shuffled_ties(&self, query: &Query, seed: u64) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let values = sort_shuffled_ties(self.score_values(query), limit, seed);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, in insertion order and without sorting. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn unsorted_similarities(&self, query: &Query) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let values = self.score_values(query);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Scores every value against the query in insertion order, without tracing the search.
+    fn score_values(&self, query: &Query) -> Vec<(&Value, f64)> {
+        let query_state = self.similarity.query_state(query);
+        self.values
+            .iter()
+            .map(|(_, value)| {
+                (
+                    value,
+                    self.similarity
+                        .similarity(&mut (), &query_state, value, query),
+                )
+            })
+            .collect()
+    }
+
+    /// Calculates the similarity score of every value to the given query, in insertion order. \
+    /// The scores are aligned with [values](SearchEngine::values), so `scores(query)[i]` is the score of the `i`-th value.
+    /// Nothing is sorted and no references are collected, which makes this the cheapest way of scoring all values. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to score the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector containing the similarity score of every value.
+    pub fn scores(&self, query: &Query) -> Vec<f64> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let scores: Vec<f64> = self
+            .values
+            .iter()
+            .map(|(_, value)| {
+                self.similarity
+                    .similarity(&mut (), &query_state, value, query)
+            })
+            .collect();
+        trace.finish(scores.len(), scores.iter().copied());
+        scores
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the given comparator. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `cmp` - The comparator determining the order of the scored values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_by<F>(&self, query: &Query, cmp: F) -> Vec<(&Value, f64)>
+    where
+        F: FnMut(&(&Value, f64), &(&Value, f64)) -> Ordering,
+    {
+        let trace = SearchTrace::start(query, self.values.len());
+        let mut values = self.score_values(query);
+        values.sort_by(cmp);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, only considering the values accepted by the predicate, with the most similar value first. \
+    /// Rejected values are skipped before scoring, so a hard filter can be combined with the fuzzy ranking
+    /// without paying for the similarity functions of values that are discarded anyway. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `predicate` - A function deciding whether a value is considered at all.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn search_filtered<P>(&self, query: &Query, predicate: P) -> Vec<(&Value, f64)>
+    where
+        P: Fn(&Value) -> bool,
+    {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let limit = self.limit;
+        let values = self
+            .values
+            .iter()
+            .filter(|(_, value)| predicate(value))
+            .map(|(_, value)| {
+                (
+                    value,
+                    self.similarity
+                        .similarity(&mut (), &query_state, value, query),
+                )
+            })
+            .collect::<Vec<_>>();
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, keeping only the most similar value of all values sharing a key.
+    /// The result is sorted with the most similar value first. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `key` - A function determining the key of a value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn search_dedup_by<K, F>(&self, query: &Query, key: F) -> Vec<(&Value, f64)>
+    where
+        K: Eq + Hash,
+        F: Fn(&Value) -> K,
+    {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let mut values = self.score_values(query);
+        sort_descending(&mut values);
+        let values = dedup_by_key(values, |value| key(value));
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Retrieves the values and their similarity scores as integer percentages, sorted like
+    /// [similarities](SearchEngine::similarities). \
+    /// The scores are clamped to `[0.0, 1.0]`, scaled to `0..=100` and rounded to the nearest integer,
+    /// with halves rounded up. A NaN score results in 0. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score in percent.
+    pub fn match_percentages(&self, query: &Query) -> Vec<(&Value, u8)> {
+        self.similarities(query)
+            .into_iter()
+            .map(|(value, similarity)| (value, to_percentage(similarity)))
+            .collect()
+    }
+
+    /// Performs a search based on the given query and groups the values by the tier their similarity score falls into. \
+    /// A value belongs to the highest tier whose boundary its score reaches.
+    /// Values scoring below the lowest boundary are omitted. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    /// * `tiers` - The lower boundaries of the tiers, in any order.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector with one tuple per tier, ordered from the highest to the lowest boundary.
+    /// The first element is the boundary of the tier, the second the values within the tier,
+    /// ranked with the most similar value first. Tiers without values are kept with an empty vector.
+    pub fn grouped_search(&self, query: &Query, tiers: &[f64]) -> Vec<(f64, Vec<&Value>)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let mut values = self.score_values(query);
+        sort_descending(&mut values);
+        let top_score = values.first().map(|(_, similarity)| *similarity);
+        let groups = group_by_tiers(values, tiers);
+        let results = groups.iter().map(|(_, group)| group.len()).sum();
+        trace.finish(results, top_score);
+        groups
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the most similar value first. \
+    /// This is identical to [similarities](SearchEngine::similarities), which uses the same order. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_descending(&self, query: &Query) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let values = sort_limited(self.score_values(query), limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Performs a search based on the given query and returns a vector of references to the values ranked
+    /// by similarity, with the most similar value first. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_descending(&self, query: &Query) -> Vec<&Value> {
+        self.similarities_descending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the least similar value first. \
+    /// It returns the same values as [similarities](SearchEngine::similarities), in the opposite order. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_ascending(&self, query: &Query) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let values = sort_limited(self.score_values(query), limit, false);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Performs a search based on the given query and returns a vector of references to the values ranked
+    /// by similarity, with the least similar value first. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values in ascending order of their similarity to the query.
+    pub fn search_ascending(&self, query: &Query) -> Vec<&Value> {
+        self.similarities_ascending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. Values with a NaN score are excluded. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_top_k(&self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let values = top_k(self.score_values(query), k);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
+    /// Performs a search based on the given query and returns references to the `k` most similar values,
+    /// with the most similar value first. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to search the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` references to the values ranked by their similarity to the query.
+    pub fn search_top_k(&self, query: &Query, k: usize) -> Vec<&Value> {
+        self.similarities_top_k(query, k)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `N` values most similar to the given query together with their similarity scores,
+    /// like [similarities_top_k](SearchEngine::similarities_top_k), but without allocating on the heap. \
+    /// The slots are filled with the most similar value first, followed by `None` if there are fewer than `N` values
+    /// with a score other than NaN. This is meant for small `N`, e.g. the suggestions of an autocomplete. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns an array of `N` slots and the number of filled slots.
+    pub fn top_n<const N: usize>(&self, query: &Query) -> ([Option<(&Value, f64)>; N], usize) {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let (slots, filled) = top_n(self.values.iter().map(|(_, value)| {
+            let similarity = self
+                .similarity
+                .similarity(&mut (), &query_state, value, query);
+            (value, similarity)
+        }));
+        trace.finish(filled, scores_of(slots.iter().flatten()));
+        (slots, filled)
+    }
+
+    /// Retrieves the `k` most similar values of a random sample, together with their similarity scores,
+    /// sorted with the most similar value first. \
+    /// Only `sample` values are scored, so the cost doesn't depend on the size of the search engine,
+    /// but the results are approximate: a value outside of the sample is never returned,
+    /// even if it is the best match. The larger the sample in relation to all values,
+    /// the more likely the true top results are found. The sample only depends on the seed. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `sample` - The number of randomly chosen values to score.
+    /// * `k` - The maximum number of values to return.
+    /// * `seed` - The seed of the random sample.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn approx_search(
+        &self,
+        query: &Query,
+        sample: usize,
+        k: usize,
+        seed: u64,
+    ) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let indices = sample_indices(self.values.len(), sample, seed);
+        let query_state = self.similarity.query_state(query);
+        let values: Vec<_> = indices
+            .into_iter()
+            .map(|index| {
+                let value = &self.values[index].1;
+                (
+                    value,
+                    self.similarity
+                        .similarity(&mut (), &query_state, value, query),
+                )
+            })
+            .collect();
+        let values = top_k(values, k);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+
     /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
-    /// to the given query. \
-    /// This version of the function is used, if the search engine contains stateful functions.
+    /// to the given query, excluding all values with a score below `threshold` or a NaN score,
+    /// with the most similar value first. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
     ///
     /// # Arguments
     ///
     /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
     ///
     /// # Returns
     ///
     /// Returns a vector of tuples where the first element is a reference to a value and the second element
     /// is its similarity score as a floating-point number.
-    pub fn similarities(&mut self, query: &Query) -> Vec<(&Value, f64)> {
-        let mut values = self
-            .values
-            .iter_mut()
-            .map(|(state, value)| {
-                (
-                    value as &Value,
-                    self.similarity.similarity(state, value, query),
-                )
-            })
-            .collect::<Vec<_>>();
-        values.sort_unstable_by(|(_, v), (_, s)| v.partial_cmp(s).unwrap_or(Ordering::Equal));
+    pub fn similarities_above(&self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let limit = self.limit;
+        let values = above(self.score_values(query), threshold);
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
         values
     }
 
     /// Performs a search based on the given query and returns a vector of references to the values ranked
-    /// by similarity. \
-    /// This version of the function is used, if the search engine contains stateful functions.
+    /// by similarity, excluding all values with a score below `threshold` or a NaN score. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
     ///
     /// # Arguments
     ///
     /// * `query` - The query against which to search the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
     ///
     /// # Returns
     ///
     /// Returns a vector of references to the values ranked by their similarity to the query.
-    pub fn search(&mut self, query: &Query) -> Vec<&Value> {
-        self.similarities(query).into_iter().map(|v| v.0).collect()
+    pub fn search_above(&self, query: &Query, threshold: f64) -> Vec<&Value> {
+        self.similarities_above(query, threshold)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
     }
-}
 
-impl<Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Immutable>
-where
-    S: Similarity<Value, Query, State = ()>,
-{
-    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
-    /// to the given query. \
+    /// Returns the most similar value to the given query together with its similarity score,
+    /// scoring the values in a single pass without collecting or sorting them. \
+    /// Of multiple equally similar values the one added first is returned, values with a NaN score are ignored. \
     /// This version of the function is used, if the search engine contains no stateful functions.
     ///
     /// # Arguments
     ///
-    /// * `query` - The query against which to rank the values.
+    /// * `query` - The query against which to compare the values.
     ///
     /// # Returns
     ///
-    /// Returns a vector of tuples where the first element is a reference to a value and the second element
-    /// is its similarity score as a floating-point number.
-    pub fn similarities(&self, query: &Query) -> Vec<(&Value, f64)> {
-        let mut values = self
-            .values
-            .iter()
-            .map(|(_, value)| (value, self.similarity.similarity(&mut (), value, query)))
-            .collect::<Vec<_>>();
-        values.sort_unstable_by(|(_, v), (_, s)| v.partial_cmp(s).unwrap_or(Ordering::Equal));
-        values
+    /// Returns the best match, or `None` if the search engine contains no values.
+    pub fn best_match(&self, query: &Query) -> Option<(&Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let best = best(self.values.iter().map(|(_, value)| {
+            let similarity = self
+                .similarity
+                .similarity(&mut (), &query_state, value, query);
+            (value, similarity)
+        }));
+        trace.finish(best.iter().count(), scores_of(&best));
+        best
     }
 
-    /// Performs a search based on the given query and returns a vector of references to the values ranked
-    /// by similarity. \
+    /// Returns the most similar value to the given query together with its similarity score,
+    /// if its score is at least `threshold`. \
     /// This version of the function is used, if the search engine contains no stateful functions.
     ///
     /// # Arguments
     ///
-    /// * `query` - The query against which to search the values.
+    /// * `query` - The query against which to compare the values.
+    /// * `threshold` - The minimum similarity score of the returned value.
     ///
     /// # Returns
     ///
-    /// Returns a vector of references to the values ranked by their similarity to the query.
-    pub fn search(&self, query: &Query) -> Vec<&Value> {
-        self.similarities(query).into_iter().map(|v| v.0).collect()
+    /// Returns the best match, or `None` if no value reaches the threshold.
+    pub fn best_match_above(&self, query: &Query, threshold: f64) -> Option<(&Value, f64)> {
+        self.best_match(query)
+            .filter(|(_, similarity)| *similarity >= threshold)
+    }
+
+    /// Counts the values with a similarity score of at least `threshold` to the given query,
+    /// without collecting or sorting them. Values with a NaN score are not counted. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to score the values.
+    /// * `threshold` - The minimum similarity score a value must have to be counted.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of values scoring at least `threshold`.
+    pub fn count_above(&self, query: &Query, threshold: f64) -> usize {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let count = self
+            .values
+            .iter()
+            .filter(|(_, value)| {
+                self.similarity
+                    .similarity(&mut (), &query_state, value, query)
+                    >= threshold
+            })
+            .count();
+        trace.finish(count, None);
+        count
+    }
+}
+
+/// Scores the values against the query and returns the `k` most similar ones, without building a [`SearchEngine`].
+/// The values are consumed one by one and only the current top `k` are kept in memory.
+///
+/// ```rust
+/// use simple_search::search_engine::search_iter;
+/// use simple_search::levenshtein::base::normalized_levenshtein_similarity;
+///
+/// let results = search_iter(["apple", "banana", "cherry"], "banan", |v, q| {
+///     normalized_levenshtein_similarity(q, v)
+/// }, 1);
+/// assert_eq!(results[0].0, "banana");
+/// ```
+///
+/// # Arguments
+///
+/// * `values` - The values to search.
+/// * `query` - The query against which to rank the values.
+/// * `metric` - A function for determining the similarity between a value and the query.
+/// * `k` - The maximum number of results.
+///
+/// # Returns
+///
+/// Returns a vector of up to `k` tuples of the values and their similarity scores, with the most similar value first.
+pub fn search_iter<V, I: IntoIterator<Item = V>>(
+    values: I,
+    query: &str,
+    metric: impl Fn(&V, &str) -> f64,
+    k: usize,
+) -> Vec<(V, f64)> {
+    top_k(
+        values.into_iter().map(|value| {
+            let similarity = metric(&value, query);
+            (value, similarity)
+        }),
+        k,
+    )
+}
+
+/// The maximum difference between two scores for them to be considered equal by [`SearchResult`].
+pub const SCORE_EPSILON: f64 = 1e-9;
+
+/// A value together with its similarity score, as returned by the search functions.
+///
+/// Two results are equal if their values are equal and their scores differ by at most [`SCORE_EPSILON`].
+/// As this equality isn't transitive, results implement [PartialEq] but not [Eq].
+/// This allows comparing whole result lists directly, e.g. in tests: \
+/// ```rust
+/// use simple_search::search_engine::{SearchEngine, SearchResult};
+///
+/// let engine = SearchEngine::new()
+///     .with_values(vec!["a", "b"])
+///     .with(|v: &&str, q: &str| if *v == q { 1. } else { 0.1 + 0.2 });
+///
+/// let results: Vec<_> = engine.similarities("b").into_iter().map(SearchResult::from).collect();
+/// assert_eq!(results, vec![SearchResult::new(&"b", 1.), SearchResult::new(&"a", 0.3)]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SearchResult<Value> {
+    pub value: Value,
+    pub score: f64,
+}
+
+impl<Value> SearchResult<Value> {
+    /// Creates a new `SearchResult` from a value and its score.
+    pub fn new(value: Value, score: f64) -> Self {
+        Self { value, score }
+    }
+
+    /// Compares two results, allowing their scores to differ by at most `epsilon`.
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool
+    where
+        Value: PartialEq,
+    {
+        self.value == other.value && (self.score - other.score).abs() <= epsilon
+    }
+}
+
+impl<Value> From<(Value, f64)> for SearchResult<Value> {
+    fn from((value, score): (Value, f64)) -> Self {
+        Self { value, score }
+    }
+}
+
+impl<Value: PartialEq> PartialEq for SearchResult<Value> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, SCORE_EPSILON)
     }
 }
 
@@ -371,11 +2504,346 @@ where
         Self {
             values: self.values.clone(),
             similarity: self.similarity.clone(),
+            limit: self.limit,
+            sorted_unique: self.sorted_unique,
             phantom: Default::default(),
         }
     }
 }
 
+/// Compares two similarity scores, treating NaN as less similar than any other score.
+/// All sorting functions of the search engines use this comparator,
+/// so borrowing, consuming and parallel searches always agree on the order.
+pub(crate) fn compare_similarities(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// Sorts the tuples by their similarity score, with the least similar value first.
+/// Tuples with equal scores keep their relative order.
+pub(crate) fn sort_ascending<Value>(values: &mut [(Value, f64)]) {
+    values.sort_by(|(_, v), (_, s)| compare_similarities(*v, *s));
+}
+
+/// Sorts the tuples by their similarity score, with the most similar value first.
+/// Tuples with equal scores keep their relative order.
+pub(crate) fn sort_descending<Value>(values: &mut [(Value, f64)]) {
+    values.sort_by(|(_, v), (_, s)| compare_similarities(*s, *v));
+}
+
+/// Measures a search, to emit a debug event describing it once it is finished,
+/// with the size of the query in bytes, the number of values, the number of results, the elapsed time and the best score. \
+/// Without the `tracing` feature, it does nothing.
+pub(crate) struct SearchTrace {
+    #[cfg(feature = "tracing")]
+    query_len: usize,
+    #[cfg(feature = "tracing")]
+    values: usize,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+impl SearchTrace {
+    /// Starts measuring a search for the query over the given number of values.
+    pub(crate) fn start<Query: ?Sized>(query: &Query, values: usize) -> Self {
+        #[cfg(feature = "tracing")]
+        return Self {
+            query_len: std::mem::size_of_val(query),
+            values,
+            start: std::time::Instant::now(),
+        };
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (query, values);
+            Self {}
+        }
+    }
+
+    /// Emits the event of the finished search. The scores are only consumed with the `tracing` feature,
+    /// so lazily mapping the results to their scores costs nothing without it.
+    pub(crate) fn finish(self, results: usize, scores: impl IntoIterator<Item = f64>) {
+        #[cfg(feature = "tracing")]
+        {
+            let top_score = scores
+                .into_iter()
+                .filter(|similarity| !similarity.is_nan())
+                .reduce(f64::max);
+            tracing::debug!(
+                query_len = self.query_len,
+                values = self.values,
+                results,
+                elapsed_us = self.start.elapsed().as_micros() as u64,
+                top_score,
+                "search"
+            );
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = (results, scores);
+    }
+}
+
+/// The scores of the results, for [SearchTrace::finish].
+#[allow(clippy::type_complexity)]
+pub(crate) fn scores_of<'a, Value: 'a, I>(
+    results: I,
+) -> std::iter::Map<I::IntoIter, fn(&'a (Value, f64)) -> f64>
+where
+    I: IntoIterator<Item = &'a (Value, f64)>,
+{
+    results.into_iter().map(|(_, similarity)| *similarity)
+}
+
+/// Sorts the tuples by their similarity score and, if a limit is given,
+/// keeps only the `limit` most similar tuples, selected with a bounded heap.
+pub(crate) fn sort_limited<Value>(
+    mut values: Vec<(Value, f64)>,
+    limit: Option<usize>,
+    descending: bool,
+) -> Vec<(Value, f64)> {
+    match limit {
+        Some(limit) => {
+            let mut values = top_k(values, limit);
+            if !descending {
+                sort_ascending(&mut values);
+            }
+            values
+        }
+        None if descending => {
+            sort_descending(&mut values);
+            values
+        }
+        None => {
+            sort_ascending(&mut values);
+            values
+        }
+    }
+}
+
+/// Keeps only the first tuple of all tuples sharing a key, preserving the order of the tuples.
+pub(crate) fn dedup_by_key<Value, K: Eq + Hash>(
+    values: Vec<(Value, f64)>,
+    key: impl Fn(&Value) -> K,
+) -> Vec<(Value, f64)> {
+    let mut seen = HashSet::new();
+    values
+        .into_iter()
+        .filter(|(value, _)| seen.insert(key(value)))
+        .collect()
+}
+
+/// Groups tuples sorted with the most similar value first by the highest tier boundary their score reaches.
+pub(crate) fn group_by_tiers<Value>(
+    values: Vec<(Value, f64)>,
+    tiers: &[f64],
+) -> Vec<(f64, Vec<Value>)> {
+    let mut groups: Vec<(f64, Vec<Value>)> = tiers.iter().map(|tier| (*tier, Vec::new())).collect();
+    groups.sort_by(|(a, _), (b, _)| compare_similarities(*b, *a));
+
+    for (value, similarity) in values {
+        if let Some((_, group)) = groups.iter_mut().find(|(tier, _)| similarity >= *tier) {
+            group.push(value);
+        }
+    }
+    groups
+}
+
+/// Converts a similarity score into an integer percentage, clamping it to `[0.0, 1.0]`.
+pub(crate) fn to_percentage(similarity: f64) -> u8 {
+    // Casting NaN to an integer results in 0.
+    (similarity.clamp(0., 1.) * 100.).round() as u8
+}
+
+/// A small seedable [SplitMix64](https://prng.di.unimi.it/splitmix64.c) generator,
+/// used where results have to be random but reproducible.
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in `0..=max`.
+    pub(crate) fn up_to(&mut self, max: usize) -> usize {
+        (self.next_u64() % (max as u64 + 1)) as usize
+    }
+}
+
+/// Shuffles every run of equal similarity scores in place, using a [SplitMix64] generator seeded with `seed`.
+/// NaN scores are considered equal to each other.
+pub(crate) fn shuffle_ties<Value>(values: &mut [(Value, f64)], seed: u64) {
+    let mut rng = SplitMix64::new(seed);
+    for group in values.chunk_by_mut(|a, b| compare_similarities(a.1, b.1) == Ordering::Equal) {
+        for i in (1..group.len()).rev() {
+            let j = rng.up_to(i);
+            group.swap(i, j);
+        }
+    }
+}
+
+/// Sorts the tuples with the most similar value first and shuffles every run of equal scores like [shuffle_ties],
+/// before keeping only the `limit` most similar tuples. \
+/// The run straddling the limit is shuffled as a whole, so every tuple of it has the same chance to be kept.
+pub(crate) fn sort_shuffled_ties<Value>(
+    mut values: Vec<(Value, f64)>,
+    limit: Option<usize>,
+    seed: u64,
+) -> Vec<(Value, f64)> {
+    sort_descending(&mut values);
+    if let Some(limit) = limit {
+        // Only the runs up to the one of the last kept tuple have to be shuffled.
+        let end = match limit.checked_sub(1).and_then(|last| values.get(last)) {
+            Some((_, boundary)) => {
+                let boundary = *boundary;
+                limit
+                    + values[limit..]
+                        .iter()
+                        .take_while(|(_, similarity)| {
+                            compare_similarities(*similarity, boundary) == Ordering::Equal
+                        })
+                        .count()
+            }
+            None => limit,
+        };
+        values.truncate(end);
+        shuffle_ties(&mut values, seed);
+        values.truncate(limit);
+    } else {
+        shuffle_ties(&mut values, seed);
+    }
+    values
+}
+
+/// Draws `sample` distinct indices in `0..len` with Floyd's algorithm, which only needs memory for the sample,
+/// and returns them in ascending order. If `sample` is at least `len`, all indices are returned.
+pub(crate) fn sample_indices(len: usize, sample: usize, seed: u64) -> Vec<usize> {
+    if sample >= len {
+        return (0..len).collect();
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let mut indices = HashSet::with_capacity(sample);
+    for j in len - sample..len {
+        let t = rng.up_to(j);
+        if !indices.insert(t) {
+            indices.insert(j);
+        }
+    }
+
+    let mut indices: Vec<_> = indices.into_iter().collect();
+    indices.sort_unstable();
+    indices
+}
+
+/// Removes all tuples with a similarity score below `threshold` or a NaN score.
+pub(crate) fn above<Value>(values: Vec<(Value, f64)>, threshold: f64) -> Vec<(Value, f64)> {
+    values
+        .into_iter()
+        .filter(|(_, similarity)| *similarity >= threshold)
+        .collect()
+}
+
+/// A tuple ordered by its similarity score and then its position, used to select the top k tuples in a [BinaryHeap].
+struct Scored<Value>(Value, f64, usize);
+
+impl<Value> PartialEq for Scored<Value> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Value> Eq for Scored<Value> {}
+
+impl<Value> PartialOrd for Scored<Value> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Value> Ord for Scored<Value> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, so the heap pops the least similar tuple first,
+        // and the later one of two equally similar tuples.
+        compare_similarities(other.1, self.1).then(self.2.cmp(&other.2))
+    }
+}
+
+/// Selects the `k` most similar tuples, sorted with the most similar value first.
+/// Tuples with equal scores keep their relative order. Tuples with a NaN score are excluded.
+pub(crate) fn top_k<Value>(
+    values: impl IntoIterator<Item = (Value, f64)>,
+    k: usize,
+) -> Vec<(Value, f64)> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for (index, (value, similarity)) in values.into_iter().enumerate() {
+        if similarity.is_nan() {
+            continue;
+        }
+        heap.push(Scored(value, similarity, index));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Scored(value, similarity, _)| (value, similarity))
+        .collect()
+}
+
+/// Selects the most similar tuple in a single pass, without collecting the tuples.
+/// Of equally similar tuples the first is kept. Tuples with a NaN score are ignored.
+pub(crate) fn best<Value>(values: impl IntoIterator<Item = (Value, f64)>) -> Option<(Value, f64)> {
+    values
+        .into_iter()
+        .filter(|(_, similarity)| !similarity.is_nan())
+        .fold(None, |best, (value, similarity)| match best {
+            Some((_, best_similarity)) if best_similarity >= similarity => best,
+            _ => Some((value, similarity)),
+        })
+}
+
+/// Selects the `N` most similar tuples into an array like [top_k], sorted with the most similar value first,
+/// and returns them with the number of filled slots.
+pub(crate) fn top_n<Value, const N: usize>(
+    values: impl IntoIterator<Item = (Value, f64)>,
+) -> ([Option<(Value, f64)>; N], usize) {
+    let mut slots: [Option<(Value, f64)>; N] = std::array::from_fn(|_| None);
+    let mut filled = 0;
+    for (value, similarity) in values {
+        if similarity.is_nan() {
+            continue;
+        }
+        // Ties are inserted after the values already present, so earlier values win.
+        let position = slots[..filled]
+            .iter()
+            .position(|slot| slot.as_ref().is_some_and(|(_, s)| *s < similarity))
+            .unwrap_or(filled);
+        if position == N {
+            continue;
+        }
+        filled = (filled + 1).min(N);
+        slots[position..filled].rotate_right(1);
+        slots[position] = Some((value, similarity));
+    }
+    (slots, filled)
+}
+
 #[cfg(feature = "rayon")]
 impl<Value, Query: ?Sized, S, M: Mutability> SearchEngine<Value, Query, S, M>
 where
@@ -383,9 +2851,10 @@ where
     Query: Send + Sync,
     S: Similarity<Value, Query> + Send + Sync,
     S::State: Send + Sync,
+    S::QueryState: Send + Sync,
 {
     /// Retrieves a sorted vector of tuples containing the values and their similarity scores
-    /// to the given query. This is the parallelized version of [into_similarities](SearchEngine::into_similarities).
+    /// to the given query, with the most similar value first. This is the parallelized version of [into_similarities](SearchEngine::into_similarities).
     ///
     /// # Arguments
     ///
@@ -396,20 +2865,26 @@ where
     /// Returns a vector of tuples where the first element is a reference to a value and the second element
     /// is its similarity score as a floating-point number.
     pub fn into_par_similarities(self, query: &Query) -> Vec<(Value, f64)> {
-        let mut values = self
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let limit = self.limit;
+        let values = self
             .values
             .into_par_iter()
             .map(|(mut state, value)| {
-                let similarity = self.similarity.similarity(&mut state, &value, query);
+                let similarity =
+                    self.similarity
+                        .similarity(&mut state, &query_state, &value, query);
                 (value, similarity)
             })
             .collect::<Vec<_>>();
-        values.sort_unstable_by(|(_, v), (_, s)| v.partial_cmp(s).unwrap_or(Ordering::Equal));
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
         values
     }
 
     /// Performs a parallel search based on the given query and returns a vector of the values ranked
-    /// by similarity. This is the parallelized version of [search](SearchEngine::search).
+    /// by similarity, with the most similar value first. This is the parallelized version of [search](SearchEngine::search).
     ///
     /// # Arguments
     ///
@@ -424,6 +2899,37 @@ where
             .map(|v| v.0)
             .collect()
     }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. Values with a NaN score are excluded. \
+    /// The values are scored in parallel and only the best `k` are kept, without sorting all values.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn into_par_top_k(self, query: &Query, k: usize) -> Vec<(Value, f64)> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let values = self
+            .values
+            .into_par_iter()
+            .map(|(mut state, value)| {
+                let similarity =
+                    self.similarity
+                        .similarity(&mut state, &query_state, &value, query);
+                (value, similarity)
+            })
+            .collect::<Vec<_>>();
+        let values = top_k(values, k);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
 }
 
 #[cfg(feature = "rayon")]
@@ -433,9 +2939,10 @@ where
     Query: Send + Sync,
     S: Similarity<Value, Query> + Send + Sync,
     S::State: Send + Sync,
+    S::QueryState: Send + Sync,
 {
     /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
-    /// to the given query. This is the parallelized version of [similarities](SearchEngine::similarities).
+    /// to the given query, with the most similar value first. This is the parallelized version of [similarities](SearchEngine::similarities).
     ///
     /// # Arguments
     ///
@@ -446,22 +2953,27 @@ where
     /// Returns a vector of tuples where the first element is a reference to a value and the second element
     /// is its similarity score as a floating-point number.
     pub fn par_similarities(&mut self, query: &Query) -> Vec<(&Value, f64)> {
-        let mut values = self
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let limit = self.limit;
+        let values = self
             .values
             .par_iter_mut()
             .map(|(state, value)| {
                 (
                     value as &Value,
-                    self.similarity.similarity(state, value, query),
+                    self.similarity
+                        .similarity(state, &query_state, value, query),
                 )
             })
             .collect::<Vec<_>>();
-        values.sort_unstable_by(|(_, v), (_, s)| v.partial_cmp(s).unwrap_or(Ordering::Equal));
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
         values
     }
 
     /// Performs a parallelized search based on the given query and returns a vector of the values ranked
-    /// by similarity. This is the parallelized version of [search](SearchEngine::search).
+    /// by similarity, with the most similar value first. This is the parallelized version of [search](SearchEngine::search).
     ///
     /// # Arguments
     ///
@@ -476,6 +2988,31 @@ where
             .map(|v| v.0)
             .collect()
     }
+
+    /// Calculates the similarity score of every value to the given query in parallel, in insertion order.
+    /// This is the parallelized version of [scores](SearchEngine::scores).
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to score the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector containing the similarity score of every value.
+    pub fn par_scores(&mut self, query: &Query) -> Vec<f64> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let scores: Vec<f64> = self
+            .values
+            .par_iter_mut()
+            .map(|(state, value)| {
+                self.similarity
+                    .similarity(state, &query_state, value, query)
+            })
+            .collect();
+        trace.finish(scores.len(), scores.iter().copied());
+        scores
+    }
 }
 
 #[cfg(feature = "rayon")]
@@ -485,9 +3022,10 @@ where
     Query: Send + Sync,
     S: Similarity<Value, Query, State = ()> + Send + Sync,
     S::State: Send + Sync,
+    S::QueryState: Send + Sync,
 {
     /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
-    /// to the given query. This is the parallelized version of [similarities](SearchEngine::similarities).
+    /// to the given query, with the most similar value first. This is the parallelized version of [similarities](SearchEngine::similarities).
     ///
     /// # Arguments
     ///
@@ -498,17 +3036,27 @@ where
     /// Returns a vector of tuples where the first element is a reference to a value and the second element
     /// is its similarity score as a floating-point number.
     pub fn par_similarities(&self, query: &Query) -> Vec<(&Value, f64)> {
-        let mut values = self
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let limit = self.limit;
+        let values = self
             .values
             .par_iter()
-            .map(|(_, value)| (value, self.similarity.similarity(&mut (), value, query)))
+            .map(|(_, value)| {
+                (
+                    value,
+                    self.similarity
+                        .similarity(&mut (), &query_state, value, query),
+                )
+            })
             .collect::<Vec<_>>();
-        values.sort_unstable_by(|(_, v), (_, s)| v.partial_cmp(s).unwrap_or(Ordering::Equal));
+        let values = sort_limited(values, limit, true);
+        trace.finish(values.len(), scores_of(&values));
         values
     }
 
     /// Performs a parallelized search based on the given query and returns a vector of the values ranked
-    /// by similarity. This is the parallelized version of [search](SearchEngine::search).
+    /// by similarity, with the most similar value first. This is the parallelized version of [search](SearchEngine::search).
     ///
     /// # Arguments
     ///
@@ -523,4 +3071,96 @@ where
             .map(|v| v.0)
             .collect()
     }
+
+    /// Calculates the similarity score of every value to the given query in parallel, in insertion order.
+    /// This is the parallelized version of [scores](SearchEngine::scores).
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to score the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector containing the similarity score of every value.
+    pub fn par_scores(&self, query: &Query) -> Vec<f64> {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let scores: Vec<f64> = self
+            .values
+            .par_iter()
+            .map(|(_, value)| {
+                self.similarity
+                    .similarity(&mut (), &query_state, value, query)
+            })
+            .collect();
+        trace.finish(scores.len(), scores.iter().copied());
+        scores
+    }
+
+    /// Counts the values with a similarity score of at least `threshold` to the given query in parallel.
+    /// This is the parallelized version of [count_above](SearchEngine::count_above).
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to score the values.
+    /// * `threshold` - The minimum similarity score a value must have to be counted.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of values scoring at least `threshold`.
+    pub fn par_count_above(&self, query: &Query, threshold: f64) -> usize {
+        let trace = SearchTrace::start(query, self.values.len());
+        let query_state = self.similarity.query_state(query);
+        let count = self
+            .values
+            .par_iter()
+            .filter(|(_, value)| {
+                self.similarity
+                    .similarity(&mut (), &query_state, value, query)
+                    >= threshold
+            })
+            .count();
+        trace.finish(count, None);
+        count
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Immutable>
+where
+    Value: Sync,
+    Query: Sync,
+    S: Similarity<Value, Query, State = ()>,
+{
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, with the most similar value first, scoring the values in parallel with the given function. \
+    /// The configured similarity functions are not used, so they don't have to be [Send] or [Sync].
+    /// This allows passing state, that is only valid for a single query, such as a cache borrowed for the call.
+    ///
+    /// The function is shared by all threads of the rayon thread pool, so it must be [Sync],
+    /// just like the values and the query it receives.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `function` - A function for determining the similarity between a value and the query.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn par_similarities_with<F>(&self, query: &Query, function: &F) -> Vec<(&Value, f64)>
+    where
+        F: Fn(&Value, &Query) -> f64 + Sync,
+    {
+        let trace = SearchTrace::start(query, self.values.len());
+        let values = self
+            .values
+            .par_iter()
+            .map(|(_, value)| (value, function(value, query)))
+            .collect::<Vec<_>>();
+        let values = sort_limited(values, self.limit, true);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
 }
@@ -0,0 +1,148 @@
+//! This module provides the [`Searchable`] trait, which lets a value supply its own searchable text,
+//! instead of extracting it with a closure for every search engine.
+
+use std::borrow::Cow;
+
+use crate::levenshtein::base::normalized_levenshtein_similarity;
+use crate::search_engine::{
+    scores_of, top_k, Immutable, Mutability, Mutable, SearchEngine, SearchTrace,
+};
+use crate::similarity::{Similarity, StatelessCombination};
+
+/// A value providing the text it is searched by.
+pub trait Searchable {
+    /// Returns the text the value is compared against the query with.
+    fn search_text(&self) -> Cow<'_, str>;
+}
+
+impl Searchable for String {
+    fn search_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl Searchable for &str {
+    fn search_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<Value, Query: ?Sized, S, M: Mutability> SearchEngine<Value, Query, S, M>
+where
+    S: Similarity<Value, Query>,
+{
+    /// Adds a function comparing the [search text](Searchable::search_text) of a value to the query,
+    /// using [normalized_levenshtein_similarity] as the default metric.
+    #[allow(clippy::type_complexity)]
+    pub fn with_searchable(
+        self,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatelessCombination<Value, Query, S, impl Fn(&Value, &Query) -> f64>,
+        M,
+    >
+    where
+        Value: Searchable,
+        Query: AsRef<str>,
+    {
+        self.with(|value: &Value, query: &Query| {
+            normalized_levenshtein_similarity(query.as_ref(), &value.search_text())
+        })
+    }
+}
+
+impl<Value, S> SearchEngine<Value, str, S, Mutable>
+where
+    Value: Searchable,
+    S: Similarity<Value, str>,
+{
+    /// Retrieves the `k` values most similar to the value at `index`, using its [search text](Searchable::search_text)
+    /// as the query, sorted with the most similar value first. The value itself is excluded. \
+    /// The value isn't compared to itself, so its state is left untouched
+    /// and will catch up with the next query like any other state. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the reference value, in insertion order.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn similar_to(&mut self, index: usize, k: usize) -> Vec<(&Value, f64)> {
+        let query = reference_text(self.get(index), index);
+        let trace = SearchTrace::start(query.as_str(), self.len());
+        let (similarity, values) = self.similarity_and_values_mut();
+        let query_state = similarity.query_state(&query);
+        let values = values
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .map(|(_, (state, value))| {
+                (
+                    value,
+                    similarity.similarity(state, &query_state, value, &query),
+                )
+            });
+        let values = top_k(values, k);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+}
+
+impl<Value, S> SearchEngine<Value, str, S, Immutable>
+where
+    Value: Searchable,
+    S: Similarity<Value, str, State = ()>,
+{
+    /// Retrieves the `k` values most similar to the value at `index`, using its [search text](Searchable::search_text)
+    /// as the query, sorted with the most similar value first. The value itself is excluded. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the reference value, in insertion order.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn similar_to(&self, index: usize, k: usize) -> Vec<(&Value, f64)> {
+        let query = reference_text(self.get(index), index);
+        let trace = SearchTrace::start(query.as_str(), self.len());
+        let similarity = self.similarity();
+        let query_state = similarity.query_state(&query);
+        let values = self
+            .values()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .map(|(_, value)| {
+                (
+                    value,
+                    similarity.similarity(&mut (), &query_state, value, &query),
+                )
+            });
+        let values = top_k(values, k);
+        trace.finish(values.len(), scores_of(&values));
+        values
+    }
+}
+
+/// Returns the owned search text of the reference value, so the search engine can be borrowed mutably while it is used.
+fn reference_text<Value: Searchable>(value: Option<&Value>, index: usize) -> String {
+    match value {
+        Some(value) => value.search_text().into_owned(),
+        None => panic!("index {index} is out of bounds"),
+    }
+}
@@ -0,0 +1,67 @@
+//! This module provides the [`SearchSession`] struct, which packages the "search as you type" workflow
+//! of an editor on top of a stateful [`SearchEngine`].
+
+use crate::search_engine::{Mutable, SearchEngine};
+use crate::similarity::Similarity;
+
+/// A "search as you type" session borrowed from a mutable [`SearchEngine`].
+///
+/// The session tracks the current query, which is edited one keystroke at a time.
+/// Since only the end of the query changes between keystrokes, stateful similarity functions such as
+/// [IncrementalLevenshtein](crate::levenshtein::incremental::IncrementalLevenshtein)
+/// only have to recompute the last rows of their matrices.
+pub struct SearchSession<'e, Value, S>
+where
+    S: Similarity<Value, str>,
+{
+    engine: &'e mut SearchEngine<Value, str, S, Mutable>,
+    query: String,
+}
+
+impl<'e, Value, S> SearchSession<'e, Value, S>
+where
+    S: Similarity<Value, str>,
+{
+    /// Returns the current query of the session.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Appends a char to the current query.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - The typed char.
+    pub fn type_char(&mut self, c: char) {
+        self.query.push(c);
+    }
+
+    /// Removes the last char of the current query, if there is one.
+    pub fn backspace(&mut self) {
+        self.query.pop();
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the current query, with the most similar value first.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn results(&mut self) -> Vec<(&Value, f64)> {
+        self.engine.similarities(&self.query)
+    }
+}
+
+impl<Value, S> SearchEngine<Value, str, S, Mutable>
+where
+    S: Similarity<Value, str>,
+{
+    /// Starts a [`SearchSession`] with an empty query, borrowing the search engine for its duration.
+    pub fn session(&mut self) -> SearchSession<'_, Value, S> {
+        SearchSession {
+            engine: self,
+            query: String::new(),
+        }
+    }
+}
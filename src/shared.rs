@@ -0,0 +1,138 @@
+//! This module provides [`SharedState`], a copy-on-write wrapper for the states of stateful functions.
+//!
+//! Cloning a search engine clones the state of every value, which is expensive for large states
+//! like the matrix of an [IncrementalLevenshtein](crate::levenshtein::incremental::IncrementalLevenshtein).
+//! With [with_shared_state](SearchEngine::with_shared_state) or [with_values_ref_counted](SearchEngine::with_values_ref_counted)
+//! the states are reference counted instead, so a clone only copies pointers,
+//! and a state is only copied once it is mutated in one of the engines.
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::search_engine::{Mutability, Mutable, SearchEngine};
+use crate::similarity::{Similarity, StatefulCombination};
+
+/// A reference counted state, which is shared between clones until it is mutated.
+#[derive(Debug, Default)]
+pub struct SharedState<State>(Arc<State>);
+
+impl<State> SharedState<State> {
+    /// Creates a new `SharedState`, which isn't shared yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state to wrap.
+    pub fn new(state: State) -> Self {
+        Self(Arc::new(state))
+    }
+
+    /// Returns a mutable reference to the state, copying it first if it is shared with another clone.
+    pub fn get_mut(&mut self) -> &mut State
+    where
+        State: Clone,
+    {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Returns true if the state is still shared with another clone.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.0) > 1
+    }
+
+    /// Returns true if both point to the same state.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<State> Clone for SharedState<State> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<State> Deref for SharedState<State> {
+    type Target = State;
+
+    fn deref(&self) -> &State {
+        &self.0
+    }
+}
+
+impl<Value, Query: ?Sized, S, M: Mutability> SearchEngine<Value, Query, S, M>
+where
+    S: Similarity<Value, Query>,
+{
+    /// Adds a stateful function, whose state is wrapped in a [`SharedState`]. \
+    /// The function only receives the wrapper, so it can read the state without copying it
+    /// and should only call [get_mut](SharedState::get_mut) if the state actually changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `state_function` - A function for creating the state for a value.
+    /// * `function` - A function for determining the similarity between a value and the query.
+    #[allow(clippy::type_complexity)]
+    pub fn with_shared_state<Func, StateFunc, State>(
+        self,
+        state_function: StateFunc,
+        function: Func,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatefulCombination<
+            Value,
+            Query,
+            S,
+            Func,
+            impl Fn(&Value) -> SharedState<State> + Clone,
+            SharedState<State>,
+        >,
+        Mutable,
+    >
+    where
+        Func: Fn(&mut SharedState<State>, &Value, &Query) -> f64,
+        StateFunc: Fn(&Value) -> State + Clone,
+    {
+        self.with_state(
+            move |value| SharedState::new(state_function(value)),
+            function,
+        )
+    }
+
+    /// Adds a stateful function like [with_state](SearchEngine::with_state),
+    /// but with the states of all values reference counted, so clones of the engine share them. \
+    /// The function receives the state itself, which is copied the first time it is scored in a clone.
+    /// Use [with_shared_state](SearchEngine::with_shared_state) to only copy states that actually change.
+    ///
+    /// # Arguments
+    ///
+    /// * `state_function` - A function for creating the state for a value.
+    /// * `function` - A function for determining the similarity between a value and the query.
+    #[allow(clippy::type_complexity)]
+    pub fn with_values_ref_counted<Func, StateFunc, State>(
+        self,
+        state_function: StateFunc,
+        function: Func,
+    ) -> SearchEngine<
+        Value,
+        Query,
+        StatefulCombination<
+            Value,
+            Query,
+            S,
+            impl Fn(&mut SharedState<State>, &Value, &Query) -> f64 + Clone,
+            impl Fn(&Value) -> SharedState<State> + Clone,
+            SharedState<State>,
+        >,
+        Mutable,
+    >
+    where
+        Func: Fn(&mut State, &Value, &Query) -> f64 + Clone,
+        StateFunc: Fn(&Value) -> State + Clone,
+        State: Clone,
+    {
+        self.with_shared_state(state_function, move |state, value, query| {
+            function(state.get_mut(), value, query)
+        })
+    }
+}
@@ -2,14 +2,19 @@
 //!
 //! The builder pattern is used to combine multiple similarity functions and optionally states.
 
+use std::borrow::Borrow;
 use std::marker::PhantomData;
 
+use crate::search_engine::{CombineMode, FieldAccessor, IsEmpty};
+
 pub struct StatelessCombination<Value, Query: ?Sized, Inner, Func>
 where
     Func: Fn(&Value, &Query) -> f64,
     Inner: Similarity<Value, Query>,
 {
     weight: f64,
+    mode: CombineMode,
+    label: Option<String>,
     function: Func,
     inner: Inner,
     phantom: PhantomData<(Value, Query)>,
@@ -22,18 +27,148 @@ where
     Inner: Similarity<Value, Query>,
 {
     weight: f64,
+    mode: CombineMode,
+    label: Option<String>,
     function: Func,
     state_func: StateFunc,
     inner: Inner,
     phantom: PhantomData<(Value, State, Query)>,
 }
 
+pub struct QueryStateCombination<
+    Value,
+    Query: ?Sized,
+    Inner,
+    Func,
+    StateFunc,
+    State,
+    QueryFunc,
+    QueryState,
+> where
+    Func: Fn(&mut State, &QueryState, &Value) -> f64,
+    StateFunc: Fn(&Value) -> State,
+    QueryFunc: Fn(&Query) -> QueryState,
+    Inner: Similarity<Value, Query>,
+{
+    weight: f64,
+    mode: CombineMode,
+    label: Option<String>,
+    function: Func,
+    state_func: StateFunc,
+    query_func: QueryFunc,
+    inner: Inner,
+    phantom: PhantomData<(Value, State, QueryState, Query)>,
+}
+
+pub struct LengthNormalization<Value, Query: ?Sized, Inner>
+where
+    Inner: Similarity<Value, Query>,
+{
+    strength: f64,
+    inner: Inner,
+    phantom: PhantomData<(Value, Query)>,
+}
+
+pub struct EmptyQuery<Value, Query: ?Sized, Inner>
+where
+    Inner: Similarity<Value, Query>,
+{
+    score: f64,
+    inner: Inner,
+    phantom: PhantomData<(Value, Query)>,
+}
+
+/// A field of a value, forming its own layer of a [`FieldCombination`].
+struct Field<Value> {
+    label: String,
+    weight: f64,
+    mode: CombineMode,
+    accessor: FieldAccessor<Value>,
+    /// The lowest and highest score of the field, if it is normalized.
+    range: (f64, f64),
+}
+
+pub struct FieldCombination<Value, Query: ?Sized, Inner, Metric>
+where
+    Metric: Fn(&str, &Query) -> f64,
+    Inner: Similarity<Value, Query>,
+{
+    fields: Vec<Field<Value>>,
+    metric: Metric,
+    inner: Inner,
+    phantom: PhantomData<(Value, Query)>,
+}
+
+pub struct FieldNormalization<Value, Query: ?Sized + ToOwned, Inner, Metric>
+where
+    Metric: Fn(&str, &Query) -> f64,
+    Inner: Similarity<Value, Query>,
+{
+    /// The sample queries the score ranges of the fields are determined with.
+    queries: Vec<Query::Owned>,
+    fields: FieldCombination<Value, Query, Inner, Metric>,
+}
+
 pub trait Similarity<Value, Query: ?Sized> {
     type State;
 
+    /// State derived from the query, computed once per search and shared by all values.
+    type QueryState;
+
     fn state(&self, value: &Value) -> Self::State;
 
-    fn similarity<'b>(&self, state: &mut Self::State, value: &Value, query: &'b Query) -> f64;
+    fn query_state(&self, query: &Query) -> Self::QueryState;
+
+    fn similarity(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+    ) -> f64;
+
+    /// The number of similarity functions combined, each of which forms a layer.
+    fn layers(&self) -> usize;
+
+    /// Pushes the weighted similarity of every layer, starting with layer 0.
+    fn layer_similarities(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        similarities: &mut Vec<f64>,
+    ) {
+        let mut scores = Vec::with_capacity(self.layers());
+        self.layer_scores(state, query_state, value, query, &mut scores);
+        similarities.extend(scores.into_iter().map(|(_, weighted)| weighted));
+    }
+
+    /// Pushes the raw and the weighted similarity of every layer, starting with layer 0,
+    /// and returns the similarity of the value, as [similarity](Similarity::similarity) would.
+    fn layer_scores(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        scores: &mut Vec<(f64, f64)>,
+    ) -> f64;
+
+    /// Recomputes the statistics layers derive from all values, e.g. the score ranges of normalized fields.
+    fn refresh(&mut self, values: &[&Value]);
+
+    /// Whether any layer derives statistics from all values, which then have to be refreshed whenever the values change.
+    fn uses_corpus(&self) -> bool;
+
+    /// The label of the given layer, if it was added with one.
+    fn label(&self, layer: usize) -> Option<&str>;
+
+    /// How the given layer is combined with the layers before it.
+    fn combine_mode(&self, layer: usize) -> CombineMode;
+
+    /// The weight of the given layer, where layer 0 is the first similarity function added.
+    fn weight_mut(&mut self, layer: usize) -> Option<&mut f64>;
 
     fn with<Func>(self, func: Func) -> StatelessCombination<Value, Query, Self, Func>
     where
@@ -54,12 +189,46 @@ pub trait Similarity<Value, Query: ?Sized> {
     {
         StatelessCombination {
             weight,
+            mode: CombineMode::Max,
+            label: None,
             function: func,
             inner: self,
             phantom: Default::default(),
         }
     }
 
+    fn with_weight_using<Func>(
+        self,
+        weight: f64,
+        mode: CombineMode,
+        func: Func,
+    ) -> StatelessCombination<Value, Query, Self, Func>
+    where
+        Func: Fn(&Value, &Query) -> f64,
+        Self: Sized,
+    {
+        StatelessCombination {
+            mode,
+            ..self.with_weight(weight, func)
+        }
+    }
+
+    fn with_labeled_weight<Func>(
+        self,
+        label: &str,
+        weight: f64,
+        func: Func,
+    ) -> StatelessCombination<Value, Query, Self, Func>
+    where
+        Func: Fn(&Value, &Query) -> f64,
+        Self: Sized,
+    {
+        StatelessCombination {
+            label: Some(label.to_string()),
+            ..self.with_weight(weight, func)
+        }
+    }
+
     fn with_state<State, Func, StateFunc>(
         self,
         func: Func,
@@ -86,24 +255,213 @@ pub trait Similarity<Value, Query: ?Sized> {
     {
         StatefulCombination {
             weight,
+            mode: CombineMode::Max,
+            label: None,
             function: func,
             state_func,
             inner: self,
             phantom: Default::default(),
         }
     }
+
+    fn with_state_using<State, Func, StateFunc>(
+        self,
+        weight: f64,
+        mode: CombineMode,
+        func: Func,
+        state_func: StateFunc,
+    ) -> StatefulCombination<Value, Query, Self, Func, StateFunc, State>
+    where
+        Func: Fn(&mut State, &Value, &Query) -> f64,
+        StateFunc: Fn(&Value) -> State,
+        Self: Sized,
+    {
+        StatefulCombination {
+            mode,
+            ..self.with_state_and_weight(weight, func, state_func)
+        }
+    }
+
+    fn with_labeled_state<State, Func, StateFunc>(
+        self,
+        label: &str,
+        weight: f64,
+        func: Func,
+        state_func: StateFunc,
+    ) -> StatefulCombination<Value, Query, Self, Func, StateFunc, State>
+    where
+        Func: Fn(&mut State, &Value, &Query) -> f64,
+        StateFunc: Fn(&Value) -> State,
+        Self: Sized,
+    {
+        StatefulCombination {
+            label: Some(label.to_string()),
+            ..self.with_state_and_weight(weight, func, state_func)
+        }
+    }
+
+    fn with_length_normalization(self, strength: f64) -> LengthNormalization<Value, Query, Self>
+    where
+        Value: AsRef<str>,
+        Self: Sized,
+    {
+        LengthNormalization {
+            strength,
+            inner: self,
+            phantom: Default::default(),
+        }
+    }
+
+    fn with_empty_query_score(self, score: f64) -> EmptyQuery<Value, Query, Self>
+    where
+        Query: IsEmpty,
+        Self: Sized,
+    {
+        EmptyQuery {
+            score,
+            inner: self,
+            phantom: Default::default(),
+        }
+    }
+
+    fn with_fields<Metric>(
+        self,
+        fields: Vec<(&str, f64, FieldAccessor<Value>)>,
+        mode: CombineMode,
+        metric: Metric,
+    ) -> FieldCombination<Value, Query, Self, Metric>
+    where
+        Metric: Fn(&str, &Query) -> f64,
+        Self: Sized,
+    {
+        FieldCombination {
+            fields: fields
+                .into_iter()
+                .map(|(label, weight, accessor)| Field {
+                    label: label.to_string(),
+                    weight,
+                    mode,
+                    accessor,
+                    range: (f64::INFINITY, f64::NEG_INFINITY),
+                })
+                .collect(),
+            metric,
+            inner: self,
+            phantom: Default::default(),
+        }
+    }
+
+    fn with_field_normalization<Metric>(
+        self,
+        fields: Vec<(&str, f64, FieldAccessor<Value>)>,
+        mode: CombineMode,
+        metric: Metric,
+        queries: &[&Query],
+    ) -> FieldNormalization<Value, Query, Self, Metric>
+    where
+        Metric: Fn(&str, &Query) -> f64,
+        Query: ToOwned,
+        Self: Sized,
+    {
+        FieldNormalization {
+            queries: queries.iter().map(|query| (*query).to_owned()).collect(),
+            fields: self.with_fields(fields, mode, metric),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn with_query_state<State, QueryState, Func, StateFunc, QueryFunc>(
+        self,
+        func: Func,
+        state_func: StateFunc,
+        query_func: QueryFunc,
+    ) -> QueryStateCombination<Value, Query, Self, Func, StateFunc, State, QueryFunc, QueryState>
+    where
+        Func: Fn(&mut State, &QueryState, &Value) -> f64,
+        StateFunc: Fn(&Value) -> State,
+        QueryFunc: Fn(&Query) -> QueryState,
+        Self: Sized,
+    {
+        self.with_query_state_and_weight(1., func, state_func, query_func)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn with_query_state_and_weight<State, QueryState, Func, StateFunc, QueryFunc>(
+        self,
+        weight: f64,
+        func: Func,
+        state_func: StateFunc,
+        query_func: QueryFunc,
+    ) -> QueryStateCombination<Value, Query, Self, Func, StateFunc, State, QueryFunc, QueryState>
+    where
+        Func: Fn(&mut State, &QueryState, &Value) -> f64,
+        StateFunc: Fn(&Value) -> State,
+        QueryFunc: Fn(&Query) -> QueryState,
+        Self: Sized,
+    {
+        QueryStateCombination {
+            weight,
+            mode: CombineMode::Max,
+            label: None,
+            function: func,
+            state_func,
+            query_func,
+            inner: self,
+            phantom: Default::default(),
+        }
+    }
 }
 
 impl<Value, Query: ?Sized> Similarity<Value, Query> for () {
     type State = ();
+    type QueryState = ();
+
+    fn state(&self, _value: &Value) -> Self::State {}
+
+    fn query_state(&self, _query: &Query) -> Self::QueryState {}
+
+    fn similarity(
+        &self,
+        _state: &mut Self::State,
+        _query_state: &Self::QueryState,
+        _value: &Value,
+        _query: &Query,
+    ) -> f64 {
+        0.
+    }
 
-    fn state(&self, _value: &Value) -> Self::State {
-        ()
+    fn refresh(&mut self, _values: &[&Value]) {}
+
+    fn uses_corpus(&self) -> bool {
+        false
     }
 
-    fn similarity<'b>(&self, _state: &mut Self::State, _value: &Value, _query: &'b Query) -> f64 {
+    fn layers(&self) -> usize {
+        0
+    }
+
+    fn layer_scores(
+        &self,
+        _state: &mut Self::State,
+        _query_state: &Self::QueryState,
+        _value: &Value,
+        _query: &Query,
+        _scores: &mut Vec<(f64, f64)>,
+    ) -> f64 {
         0.
     }
+
+    fn label(&self, _layer: usize) -> Option<&str> {
+        None
+    }
+
+    fn combine_mode(&self, _layer: usize) -> CombineMode {
+        CombineMode::Max
+    }
+
+    fn weight_mut(&mut self, _layer: usize) -> Option<&mut f64> {
+        None
+    }
 }
 
 impl<Value, Query: ?Sized, Inner, Func, StateFunc, State> Similarity<Value, Query>
@@ -114,18 +472,191 @@ where
     Inner: Similarity<Value, Query>,
 {
     type State = (State, Inner::State);
+    type QueryState = Inner::QueryState;
 
     fn state(&self, value: &Value) -> Self::State {
         ((self.state_func)(value), self.inner.state(value))
     }
 
-    fn similarity<'b>(&self, state: &mut Self::State, value: &Value, query: &'b Query) -> f64 {
+    fn query_state(&self, query: &Query) -> Self::QueryState {
+        self.inner.query_state(query)
+    }
+
+    fn similarity(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+    ) -> f64 {
         let (state, inner_state) = (&mut state.0, &mut state.1);
 
         let similarity = (self.function)(state, value, query) * self.weight;
-        let inner_similarity = self.inner.similarity(inner_state, value, query);
+        let inner_similarity = self
+            .inner
+            .similarity(inner_state, query_state, value, query);
+
+        self.mode
+            .combine(inner_similarity, similarity, self.inner.layers())
+    }
+
+    fn layer_scores(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        scores: &mut Vec<(f64, f64)>,
+    ) -> f64 {
+        let (state, inner_state) = (&mut state.0, &mut state.1);
+
+        let inner_similarity =
+            self.inner
+                .layer_scores(inner_state, query_state, value, query, scores);
+        let similarity = (self.function)(state, value, query);
+        scores.push((similarity, similarity * self.weight));
+
+        self.mode.combine(
+            inner_similarity,
+            similarity * self.weight,
+            self.inner.layers(),
+        )
+    }
+
+    fn refresh(&mut self, values: &[&Value]) {
+        self.inner.refresh(values);
+    }
+
+    fn uses_corpus(&self) -> bool {
+        self.inner.uses_corpus()
+    }
+
+    fn layers(&self) -> usize {
+        self.inner.layers() + 1
+    }
+
+    fn weight_mut(&mut self, layer: usize) -> Option<&mut f64> {
+        if layer == self.inner.layers() {
+            Some(&mut self.weight)
+        } else {
+            self.inner.weight_mut(layer)
+        }
+    }
 
-        similarity.max(inner_similarity)
+    fn label(&self, layer: usize) -> Option<&str> {
+        if layer == self.inner.layers() {
+            self.label.as_deref()
+        } else {
+            self.inner.label(layer)
+        }
+    }
+
+    fn combine_mode(&self, layer: usize) -> CombineMode {
+        if layer == self.inner.layers() {
+            self.mode
+        } else {
+            self.inner.combine_mode(layer)
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, Inner, Func, StateFunc, State, QueryFunc, QueryState>
+    Similarity<Value, Query>
+    for QueryStateCombination<Value, Query, Inner, Func, StateFunc, State, QueryFunc, QueryState>
+where
+    Func: Fn(&mut State, &QueryState, &Value) -> f64,
+    StateFunc: Fn(&Value) -> State,
+    QueryFunc: Fn(&Query) -> QueryState,
+    Inner: Similarity<Value, Query>,
+{
+    type State = (State, Inner::State);
+    type QueryState = (QueryState, Inner::QueryState);
+
+    fn state(&self, value: &Value) -> Self::State {
+        ((self.state_func)(value), self.inner.state(value))
+    }
+
+    fn query_state(&self, query: &Query) -> Self::QueryState {
+        ((self.query_func)(query), self.inner.query_state(query))
+    }
+
+    fn similarity(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+    ) -> f64 {
+        let (state, inner_state) = (&mut state.0, &mut state.1);
+        let (query_state, inner_query_state) = (&query_state.0, &query_state.1);
+
+        let similarity = (self.function)(state, query_state, value) * self.weight;
+        let inner_similarity = self
+            .inner
+            .similarity(inner_state, inner_query_state, value, query);
+
+        self.mode
+            .combine(inner_similarity, similarity, self.inner.layers())
+    }
+
+    fn layer_scores(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        scores: &mut Vec<(f64, f64)>,
+    ) -> f64 {
+        let (state, inner_state) = (&mut state.0, &mut state.1);
+        let (query_state, inner_query_state) = (&query_state.0, &query_state.1);
+
+        let inner_similarity =
+            self.inner
+                .layer_scores(inner_state, inner_query_state, value, query, scores);
+        let similarity = (self.function)(state, query_state, value);
+        scores.push((similarity, similarity * self.weight));
+
+        self.mode.combine(
+            inner_similarity,
+            similarity * self.weight,
+            self.inner.layers(),
+        )
+    }
+
+    fn refresh(&mut self, values: &[&Value]) {
+        self.inner.refresh(values);
+    }
+
+    fn uses_corpus(&self) -> bool {
+        self.inner.uses_corpus()
+    }
+
+    fn layers(&self) -> usize {
+        self.inner.layers() + 1
+    }
+
+    fn weight_mut(&mut self, layer: usize) -> Option<&mut f64> {
+        if layer == self.inner.layers() {
+            Some(&mut self.weight)
+        } else {
+            self.inner.weight_mut(layer)
+        }
+    }
+
+    fn label(&self, layer: usize) -> Option<&str> {
+        if layer == self.inner.layers() {
+            self.label.as_deref()
+        } else {
+            self.inner.label(layer)
+        }
+    }
+
+    fn combine_mode(&self, layer: usize) -> CombineMode {
+        if layer == self.inner.layers() {
+            self.mode
+        } else {
+            self.inner.combine_mode(layer)
+        }
     }
 }
 
@@ -136,16 +667,250 @@ where
     Inner: Similarity<Value, Query>,
 {
     type State = Inner::State;
+    type QueryState = Inner::QueryState;
 
     fn state(&self, value: &Value) -> Self::State {
         self.inner.state(value)
     }
 
-    fn similarity<'b>(&self, state: &mut Self::State, value: &Value, query: &'b Query) -> f64 {
+    fn query_state(&self, query: &Query) -> Self::QueryState {
+        self.inner.query_state(query)
+    }
+
+    fn similarity(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+    ) -> f64 {
         let similarity = (self.function)(value, query) * self.weight;
-        let inner_similarity = self.inner.similarity(state, value, query);
+        let inner_similarity = self.inner.similarity(state, query_state, value, query);
+
+        self.mode
+            .combine(inner_similarity, similarity, self.inner.layers())
+    }
+
+    fn layer_scores(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        scores: &mut Vec<(f64, f64)>,
+    ) -> f64 {
+        let inner_similarity = self
+            .inner
+            .layer_scores(state, query_state, value, query, scores);
+        let similarity = (self.function)(value, query);
+        scores.push((similarity, similarity * self.weight));
+
+        self.mode.combine(
+            inner_similarity,
+            similarity * self.weight,
+            self.inner.layers(),
+        )
+    }
+
+    fn refresh(&mut self, values: &[&Value]) {
+        self.inner.refresh(values);
+    }
+
+    fn uses_corpus(&self) -> bool {
+        self.inner.uses_corpus()
+    }
+
+    fn layers(&self) -> usize {
+        self.inner.layers() + 1
+    }
+
+    fn weight_mut(&mut self, layer: usize) -> Option<&mut f64> {
+        if layer == self.inner.layers() {
+            Some(&mut self.weight)
+        } else {
+            self.inner.weight_mut(layer)
+        }
+    }
+
+    fn label(&self, layer: usize) -> Option<&str> {
+        if layer == self.inner.layers() {
+            self.label.as_deref()
+        } else {
+            self.inner.label(layer)
+        }
+    }
+
+    fn combine_mode(&self, layer: usize) -> CombineMode {
+        if layer == self.inner.layers() {
+            self.mode
+        } else {
+            self.inner.combine_mode(layer)
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, Inner> LengthNormalization<Value, Query, Inner>
+where
+    Inner: Similarity<Value, Query>,
+{
+    /// The factor the similarity of a value with the given number of chars is multiplied with.
+    fn boost(&self, chars: usize) -> f64 {
+        1. + self.strength / chars.max(1) as f64
+    }
+}
+
+impl<Value, Query: ?Sized, Inner> Similarity<Value, Query>
+    for LengthNormalization<Value, Query, Inner>
+where
+    Value: AsRef<str>,
+    Inner: Similarity<Value, Query>,
+{
+    type State = (f64, Inner::State);
+    type QueryState = Inner::QueryState;
+
+    fn state(&self, value: &Value) -> Self::State {
+        (
+            self.boost(value.as_ref().chars().count()),
+            self.inner.state(value),
+        )
+    }
+
+    fn query_state(&self, query: &Query) -> Self::QueryState {
+        self.inner.query_state(query)
+    }
+
+    fn similarity(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+    ) -> f64 {
+        let (boost, inner_state) = (state.0, &mut state.1);
+        self.inner
+            .similarity(inner_state, query_state, value, query)
+            * boost
+    }
+
+    fn layer_scores(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        scores: &mut Vec<(f64, f64)>,
+    ) -> f64 {
+        let (boost, inner_state) = (state.0, &mut state.1);
+
+        let start = scores.len();
+        let inner_similarity =
+            self.inner
+                .layer_scores(inner_state, query_state, value, query, scores);
+        for (_, weighted) in &mut scores[start..] {
+            *weighted *= boost;
+        }
+        inner_similarity * boost
+    }
+
+    fn refresh(&mut self, values: &[&Value]) {
+        self.inner.refresh(values);
+    }
+
+    fn uses_corpus(&self) -> bool {
+        self.inner.uses_corpus()
+    }
 
-        similarity.max(inner_similarity)
+    fn layers(&self) -> usize {
+        self.inner.layers()
+    }
+
+    fn weight_mut(&mut self, layer: usize) -> Option<&mut f64> {
+        self.inner.weight_mut(layer)
+    }
+
+    fn label(&self, layer: usize) -> Option<&str> {
+        self.inner.label(layer)
+    }
+
+    fn combine_mode(&self, layer: usize) -> CombineMode {
+        self.inner.combine_mode(layer)
+    }
+}
+
+impl<Value, Query: ?Sized, Inner> Similarity<Value, Query> for EmptyQuery<Value, Query, Inner>
+where
+    Query: IsEmpty,
+    Inner: Similarity<Value, Query>,
+{
+    type State = Inner::State;
+    /// Whether the query is empty, checked once per search.
+    type QueryState = (bool, Inner::QueryState);
+
+    fn state(&self, value: &Value) -> Self::State {
+        self.inner.state(value)
+    }
+
+    fn query_state(&self, query: &Query) -> Self::QueryState {
+        (query.is_empty(), self.inner.query_state(query))
+    }
+
+    fn similarity(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+    ) -> f64 {
+        let (empty, inner_query_state) = (query_state.0, &query_state.1);
+        if empty {
+            self.score
+        } else {
+            self.inner
+                .similarity(state, inner_query_state, value, query)
+        }
+    }
+
+    fn layer_scores(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        scores: &mut Vec<(f64, f64)>,
+    ) -> f64 {
+        let (empty, inner_query_state) = (query_state.0, &query_state.1);
+        if empty {
+            // Every layer reports the fixed score, none of them was evaluated.
+            scores.extend((0..self.layers()).map(|_| (self.score, self.score)));
+            self.score
+        } else {
+            self.inner
+                .layer_scores(state, inner_query_state, value, query, scores)
+        }
+    }
+
+    fn refresh(&mut self, values: &[&Value]) {
+        self.inner.refresh(values);
+    }
+
+    fn uses_corpus(&self) -> bool {
+        self.inner.uses_corpus()
+    }
+
+    fn layers(&self) -> usize {
+        self.inner.layers()
+    }
+
+    fn weight_mut(&mut self, layer: usize) -> Option<&mut f64> {
+        self.inner.weight_mut(layer)
+    }
+
+    fn label(&self, layer: usize) -> Option<&str> {
+        self.inner.label(layer)
+    }
+
+    fn combine_mode(&self, layer: usize) -> CombineMode {
+        self.inner.combine_mode(layer)
     }
 }
 
@@ -159,6 +924,8 @@ where
     fn clone(&self) -> Self {
         Self {
             weight: self.weight,
+            mode: self.mode,
+            label: self.label.clone(),
             function: self.function.clone(),
             state_func: self.state_func.clone(),
             inner: self.inner.clone(),
@@ -175,7 +942,298 @@ where
     fn clone(&self) -> Self {
         Self {
             weight: self.weight,
+            mode: self.mode,
+            label: self.label.clone(),
+            function: self.function.clone(),
+            inner: self.inner.clone(),
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, Inner, Func, StateFunc, State, QueryFunc, QueryState> Clone
+    for QueryStateCombination<Value, Query, Inner, Func, StateFunc, State, QueryFunc, QueryState>
+where
+    Func: Fn(&mut State, &QueryState, &Value) -> f64 + Clone,
+    StateFunc: Fn(&Value) -> State + Clone,
+    QueryFunc: Fn(&Query) -> QueryState + Clone,
+    Inner: Similarity<Value, Query> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            weight: self.weight,
+            mode: self.mode,
+            label: self.label.clone(),
             function: self.function.clone(),
+            state_func: self.state_func.clone(),
+            query_func: self.query_func.clone(),
+            inner: self.inner.clone(),
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, Inner, Metric> FieldCombination<Value, Query, Inner, Metric>
+where
+    Metric: Fn(&str, &Query) -> f64,
+    Inner: Similarity<Value, Query>,
+{
+    /// The field forming the given layer, or `None` if the layer belongs to the inner similarity.
+    fn field_index(&self, layer: usize) -> Option<usize> {
+        layer.checked_sub(self.inner.layers())
+    }
+
+    /// The similarity of a field to the query, rescaled to the score range of the field if it has one.
+    fn field_similarity(&self, field: &Field<Value>, value: &Value, query: &Query) -> f64 {
+        let similarity = (self.metric)((field.accessor)(value), query);
+        let (min, max) = field.range;
+        if max > min {
+            ((similarity - min) / (max - min)).clamp(0., 1.)
+        } else {
+            // Without a range to rescale to, the score is used as is.
+            similarity
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, Inner, Metric> Similarity<Value, Query>
+    for FieldCombination<Value, Query, Inner, Metric>
+where
+    Metric: Fn(&str, &Query) -> f64,
+    Inner: Similarity<Value, Query>,
+{
+    type State = Inner::State;
+    type QueryState = Inner::QueryState;
+
+    fn state(&self, value: &Value) -> Self::State {
+        self.inner.state(value)
+    }
+
+    fn query_state(&self, query: &Query) -> Self::QueryState {
+        self.inner.query_state(query)
+    }
+
+    fn similarity(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+    ) -> f64 {
+        let layers = self.inner.layers();
+        let inner_similarity = self.inner.similarity(state, query_state, value, query);
+        self.fields
+            .iter()
+            .enumerate()
+            .fold(inner_similarity, |combined, (index, field)| {
+                let similarity = self.field_similarity(field, value, query) * field.weight;
+                field.mode.combine(combined, similarity, layers + index)
+            })
+    }
+
+    fn layer_scores(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        scores: &mut Vec<(f64, f64)>,
+    ) -> f64 {
+        let layers = self.inner.layers();
+        let inner_similarity = self
+            .inner
+            .layer_scores(state, query_state, value, query, scores);
+        self.fields
+            .iter()
+            .enumerate()
+            .fold(inner_similarity, |combined, (index, field)| {
+                let similarity = self.field_similarity(field, value, query);
+                scores.push((similarity, similarity * field.weight));
+                field
+                    .mode
+                    .combine(combined, similarity * field.weight, layers + index)
+            })
+    }
+
+    fn refresh(&mut self, values: &[&Value]) {
+        self.inner.refresh(values);
+    }
+
+    fn uses_corpus(&self) -> bool {
+        self.inner.uses_corpus()
+    }
+
+    fn layers(&self) -> usize {
+        self.inner.layers() + self.fields.len()
+    }
+
+    fn weight_mut(&mut self, layer: usize) -> Option<&mut f64> {
+        match self.field_index(layer) {
+            Some(index) => self.fields.get_mut(index).map(|field| &mut field.weight),
+            None => self.inner.weight_mut(layer),
+        }
+    }
+
+    fn label(&self, layer: usize) -> Option<&str> {
+        match self.field_index(layer) {
+            Some(index) => self.fields.get(index).map(|field| field.label.as_str()),
+            None => self.inner.label(layer),
+        }
+    }
+
+    fn combine_mode(&self, layer: usize) -> CombineMode {
+        match self.field_index(layer) {
+            Some(index) => self
+                .fields
+                .get(index)
+                .map_or(CombineMode::Max, |field| field.mode),
+            None => self.inner.combine_mode(layer),
+        }
+    }
+}
+
+impl<Value, Query: ?Sized + ToOwned, Inner, Metric> Similarity<Value, Query>
+    for FieldNormalization<Value, Query, Inner, Metric>
+where
+    Metric: Fn(&str, &Query) -> f64,
+    Inner: Similarity<Value, Query>,
+{
+    type State = Inner::State;
+    type QueryState = Inner::QueryState;
+
+    fn state(&self, value: &Value) -> Self::State {
+        self.fields.state(value)
+    }
+
+    fn query_state(&self, query: &Query) -> Self::QueryState {
+        self.fields.query_state(query)
+    }
+
+    fn similarity(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+    ) -> f64 {
+        self.fields.similarity(state, query_state, value, query)
+    }
+
+    fn layer_scores(
+        &self,
+        state: &mut Self::State,
+        query_state: &Self::QueryState,
+        value: &Value,
+        query: &Query,
+        scores: &mut Vec<(f64, f64)>,
+    ) -> f64 {
+        self.fields
+            .layer_scores(state, query_state, value, query, scores)
+    }
+
+    fn refresh(&mut self, values: &[&Value]) {
+        self.fields.refresh(values);
+        let metric = &self.fields.metric;
+        for field in &mut self.fields.fields {
+            let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+            for value in values {
+                for query in &self.queries {
+                    // min and max ignore NaN scores.
+                    let similarity = metric((field.accessor)(value), query.borrow());
+                    min = min.min(similarity);
+                    max = max.max(similarity);
+                }
+            }
+            field.range = (min, max);
+        }
+    }
+
+    fn uses_corpus(&self) -> bool {
+        true
+    }
+
+    fn layers(&self) -> usize {
+        self.fields.layers()
+    }
+
+    fn weight_mut(&mut self, layer: usize) -> Option<&mut f64> {
+        self.fields.weight_mut(layer)
+    }
+
+    fn label(&self, layer: usize) -> Option<&str> {
+        self.fields.label(layer)
+    }
+
+    fn combine_mode(&self, layer: usize) -> CombineMode {
+        self.fields.combine_mode(layer)
+    }
+}
+
+impl<Value, Query: ?Sized, Inner> Clone for LengthNormalization<Value, Query, Inner>
+where
+    Inner: Similarity<Value, Query> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            strength: self.strength,
+            inner: self.inner.clone(),
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<Value> Clone for Field<Value> {
+    fn clone(&self) -> Self {
+        Self {
+            label: self.label.clone(),
+            weight: self.weight,
+            mode: self.mode,
+            accessor: self.accessor.clone(),
+            range: self.range,
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, Inner, Metric> Clone for FieldCombination<Value, Query, Inner, Metric>
+where
+    Metric: Fn(&str, &Query) -> f64 + Clone,
+    Inner: Similarity<Value, Query> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            fields: self.fields.clone(),
+            metric: self.metric.clone(),
+            inner: self.inner.clone(),
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<Value, Query: ?Sized + ToOwned, Inner, Metric> Clone
+    for FieldNormalization<Value, Query, Inner, Metric>
+where
+    Metric: Fn(&str, &Query) -> f64 + Clone,
+    Inner: Similarity<Value, Query> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            queries: self
+                .queries
+                .iter()
+                .map(|query| query.borrow().to_owned())
+                .collect(),
+            fields: self.fields.clone(),
+        }
+    }
+}
+
+impl<Value, Query: ?Sized, Inner> Clone for EmptyQuery<Value, Query, Inner>
+where
+    Inner: Similarity<Value, Query> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            score: self.score,
             inner: self.inner.clone(),
             phantom: Default::default(),
         }
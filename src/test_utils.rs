@@ -0,0 +1,62 @@
+//! This module provides helpers for tests, which compare the results of different search engines. \
+//! It is only available with the `test-utils` feature.
+
+use std::fmt::Debug;
+
+/// Asserts that two rankings are equivalent, ignoring the order of values with equal scores. \
+/// Both rankings are split into buckets of consecutive values, whose scores differ from the first score
+/// of the bucket by at most `eps`. Matching buckets must have the same size and contain the same values,
+/// where the scores of a value may differ by at most `eps` between the rankings.
+///
+/// # Arguments
+///
+/// * `a` - The first ranking, as returned by [similarities](crate::search_engine::SearchEngine::similarities).
+/// * `b` - The second ranking.
+/// * `eps` - The maximum difference of two scores considered equal.
+///
+/// # Panics
+///
+/// Panics if the rankings are not equivalent.
+pub fn assert_rankings_equivalent<Value>(a: &[(Value, f64)], b: &[(Value, f64)], eps: f64)
+where
+    Value: PartialEq + Debug,
+{
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "the rankings contain a different number of values"
+    );
+
+    let mut start = 0;
+    while start < a.len() {
+        let end = bucket_end(a, start, eps);
+        assert_eq!(
+            end,
+            bucket_end(b, start, eps),
+            "the rankings differ in the values scored {:?} at position {start}",
+            a[start].1
+        );
+
+        let mut unmatched: Vec<_> = b[start..end].iter().collect();
+        for (value, score) in &a[start..end] {
+            let position = unmatched
+                .iter()
+                .position(|(other, other_score)| {
+                    other == value && (score - other_score).abs() <= eps
+                })
+                .unwrap_or_else(|| {
+                    panic!("{value:?} scored {score:?} has no equivalent in the second ranking")
+                });
+            unmatched.swap_remove(position);
+        }
+        start = end;
+    }
+}
+
+fn bucket_end<Value>(values: &[(Value, f64)], start: usize, eps: f64) -> usize {
+    let score = values[start].1;
+    values[start..]
+        .iter()
+        .position(|(_, other)| (other - score).abs() > eps)
+        .map_or(values.len(), |offset| start + offset)
+}
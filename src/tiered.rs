@@ -0,0 +1,167 @@
+//! This module provides the [`TieredSearchEngine`] struct, which avoids computing an expensive similarity function
+//! for values that are clearly outranked by a cheap similarity function.
+
+use crate::search_engine::{sort_descending, Immutable, Mutability, Mutable, SearchEngine};
+use crate::similarity::Similarity;
+
+/// A search engine scoring values in two tiers.
+///
+/// Every value is first scored by the similarity functions of the wrapped engine and the cheap function.
+/// Only values whose score is within `margin` of the k-th best score are then also scored by the expensive function.
+/// As with the builder pattern, the final score of a value is the maximum of all its computed scores.
+///
+/// This is a heuristic: a value outside the margin is assumed not to reach the top k through the expensive function.
+pub struct TieredSearchEngine<Value, Query: ?Sized, S, M: Mutability, Cheap, Expensive>
+where
+    S: Similarity<Value, Query>,
+    Cheap: Fn(&Value, &Query) -> f64,
+    Expensive: Fn(&Value, &Query) -> f64,
+{
+    engine: SearchEngine<Value, Query, S, M>,
+    cheap: Cheap,
+    expensive: Expensive,
+    margin: f64,
+}
+
+impl<Value, Query: ?Sized, S, M: Mutability> SearchEngine<Value, Query, S, M>
+where
+    S: Similarity<Value, Query>,
+{
+    /// Wraps the search engine into a [`TieredSearchEngine`],
+    /// which only computes `expensive` for values within `margin` of the top k.
+    ///
+    /// # Arguments
+    ///
+    /// * `cheap` - A cheap function for determining the similarity between a value and the query.
+    /// * `expensive` - An expensive function for determining the similarity between a value and the query.
+    /// * `margin` - How far below the k-th best score a value may be, to still be scored by `expensive`.
+    pub fn with_tiered<Cheap, Expensive>(
+        self,
+        cheap: Cheap,
+        expensive: Expensive,
+        margin: f64,
+    ) -> TieredSearchEngine<Value, Query, S, M, Cheap, Expensive>
+    where
+        Cheap: Fn(&Value, &Query) -> f64,
+        Expensive: Fn(&Value, &Query) -> f64,
+    {
+        TieredSearchEngine {
+            engine: self,
+            cheap,
+            expensive,
+            margin,
+        }
+    }
+}
+
+/// Rescores all values within `margin` of the k-th best score and sorts them with the most similar value first.
+fn rescore<Value>(
+    mut values: Vec<(Value, f64)>,
+    k: usize,
+    margin: f64,
+    expensive: impl Fn(&Value) -> f64,
+) -> Vec<(Value, f64)> {
+    sort_descending(&mut values);
+
+    if let Some(threshold) = k
+        .checked_sub(1)
+        .and_then(|i| values.get(i.min(values.len().saturating_sub(1))))
+        .map(|(_, similarity)| similarity - margin)
+    {
+        for (value, similarity) in values.iter_mut() {
+            if *similarity < threshold {
+                break;
+            }
+            *similarity = similarity.max(expensive(value));
+        }
+    }
+
+    sort_descending(&mut values);
+    values
+}
+
+impl<Value, Query: ?Sized, S, Cheap, Expensive>
+    TieredSearchEngine<Value, Query, S, Mutable, Cheap, Expensive>
+where
+    S: Similarity<Value, Query>,
+    Cheap: Fn(&Value, &Query) -> f64,
+    Expensive: Fn(&Value, &Query) -> f64,
+{
+    /// Retrieves a vector of tuples containing references to all values and their similarity scores to the given query,
+    /// sorted with the most similar value first.
+    /// The expensive function is only computed for values within the margin of the top `k`. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The number of top values the margin refers to.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        let cheap = &self.cheap;
+        let expensive = &self.expensive;
+        let values = self
+            .engine
+            .unsorted_similarities(query)
+            .into_iter()
+            .map(|(value, similarity)| (value, similarity.max(cheap(value, query))))
+            .collect();
+        rescore(values, k, self.margin, |value| expensive(value, query))
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    pub fn similarities_top_k(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        let mut values = self.similarities(query, k);
+        values.truncate(k);
+        values
+    }
+}
+
+impl<Value, Query: ?Sized, S, Cheap, Expensive>
+    TieredSearchEngine<Value, Query, S, Immutable, Cheap, Expensive>
+where
+    S: Similarity<Value, Query, State = ()>,
+    Cheap: Fn(&Value, &Query) -> f64,
+    Expensive: Fn(&Value, &Query) -> f64,
+{
+    /// Retrieves a vector of tuples containing references to all values and their similarity scores to the given query,
+    /// sorted with the most similar value first.
+    /// The expensive function is only computed for values within the margin of the top `k`. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The number of top values the margin refers to.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities(&self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        let values = self
+            .engine
+            .unsorted_similarities(query)
+            .into_iter()
+            .map(|(value, similarity)| (value, similarity.max((self.cheap)(value, query))))
+            .collect();
+        rescore(values, k, self.margin, |value| {
+            (self.expensive)(value, query)
+        })
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    pub fn similarities_top_k(&self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        let mut values = self.similarities(query, k);
+        values.truncate(k);
+        values
+    }
+}
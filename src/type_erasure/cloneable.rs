@@ -8,8 +8,13 @@ where
     Value: 'static,
     Query: 'static,
     S: Similarity<Value, Query, State = ()> + 'static,
-    Self: Clone,
+    Self: Clone + Send + Sync,
 {
+    /// Erases the type of a cloneable engine, so engines with different similarity functions can be stored together.
+    ///
+    /// # Returns
+    ///
+    /// Returns a wrapper around the engine, that only depends on the types of the values and the query.
     pub fn erase_type_cloneable(self) -> ImmutableSearchEngine<Value, Query> {
         ImmutableSearchEngine {
             engine: Box::new(self),
@@ -24,6 +29,11 @@ where
     S: Similarity<Value, Query> + 'static,
     Self: Clone,
 {
+    /// Erases the type of a cloneable engine, so engines with different similarity functions can be stored together.
+    ///
+    /// # Returns
+    ///
+    /// Returns a wrapper around the engine, that only depends on the types of the values and the query.
     pub fn erase_type_cloneable(self) -> MutableSearchEngine<Value, Query> {
         MutableSearchEngine {
             engine: Box::new(self),
@@ -32,7 +42,7 @@ where
 }
 
 trait ImmutableSearchEngineCloneTrait<Value, Query: ?Sized> {
-    fn clone_box(&self) -> Box<dyn ImmutableSearchEngineTrait<Value, Query>>;
+    fn clone_box(&self) -> Box<dyn ImmutableSearchEngineTrait<Value, Query> + Send + Sync>;
 }
 
 trait MutableSearchEngineCloneTrait<Value, Query: ?Sized> {
@@ -45,6 +55,12 @@ trait ImmutableSearchEngineTrait<Value, Query: ?Sized>:
     fn similarities_wrapper(&self, query: &Query) -> Vec<(&Value, f64)>;
 
     fn search_wrapper(&self, query: &Query) -> Vec<&Value>;
+
+    fn similarities_descending_wrapper(&self, query: &Query) -> Vec<(&Value, f64)>;
+
+    fn similarities_top_k_wrapper(&self, query: &Query, k: usize) -> Vec<(&Value, f64)>;
+
+    fn similarities_above_wrapper(&self, query: &Query, threshold: f64) -> Vec<(&Value, f64)>;
 }
 trait MutableSearchEngineTrait<Value, Query: ?Sized>:
     MutableSearchEngineCloneTrait<Value, Query>
@@ -52,6 +68,12 @@ trait MutableSearchEngineTrait<Value, Query: ?Sized>:
     fn similarities_wrapper(&mut self, query: &Query) -> Vec<(&Value, f64)>;
 
     fn search_wrapper(&mut self, query: &Query) -> Vec<&Value>;
+
+    fn similarities_descending_wrapper(&mut self, query: &Query) -> Vec<(&Value, f64)>;
+
+    fn similarities_top_k_wrapper(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)>;
+
+    fn similarities_above_wrapper(&mut self, query: &Query, threshold: f64) -> Vec<(&Value, f64)>;
 }
 
 impl<Value, Query: ?Sized, S> ImmutableSearchEngineCloneTrait<Value, Query>
@@ -61,9 +83,9 @@ where
     Query: 'static,
     S: 'static,
     S: Similarity<Value, Query, State = ()>,
-    Self: Clone,
+    Self: Clone + Send + Sync,
 {
-    fn clone_box(&self) -> Box<dyn ImmutableSearchEngineTrait<Value, Query>> {
+    fn clone_box(&self) -> Box<dyn ImmutableSearchEngineTrait<Value, Query> + Send + Sync> {
         Box::new(self.clone())
     }
 }
@@ -74,7 +96,7 @@ where
     Query: 'static,
     S: 'static,
     S: Similarity<Value, Query, State = ()>,
-    Self: Clone,
+    Self: Clone + Send + Sync,
 {
     fn similarities_wrapper(&self, query: &Query) -> Vec<(&Value, f64)> {
         self.similarities(query)
@@ -83,6 +105,18 @@ where
     fn search_wrapper(&self, query: &Query) -> Vec<&Value> {
         <SearchEngine<Value, Query, S, Immutable>>::search(self, query)
     }
+
+    fn similarities_descending_wrapper(&self, query: &Query) -> Vec<(&Value, f64)> {
+        self.similarities_descending(query)
+    }
+
+    fn similarities_top_k_wrapper(&self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.similarities_top_k(query, k)
+    }
+
+    fn similarities_above_wrapper(&self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.similarities_above(query, threshold)
+    }
 }
 
 impl<Value, Query: ?Sized, S> MutableSearchEngineCloneTrait<Value, Query>
@@ -115,21 +149,156 @@ where
     fn search_wrapper(&mut self, query: &Query) -> Vec<&Value> {
         self.search(query)
     }
+
+    fn similarities_descending_wrapper(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        self.similarities_descending(query)
+    }
+
+    fn similarities_top_k_wrapper(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.similarities_top_k(query, k)
+    }
+
+    fn similarities_above_wrapper(&mut self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.similarities_above(query, threshold)
+    }
 }
 
-/// Wrapper struct for type erased search engines not requiring mutable access due to being stateless.
+/// Wrapper struct for type erased search engines not requiring mutable access due to being stateless. \
+/// It is [Send] + [Sync], so it can be queried concurrently from multiple threads, e.g. by wrapping it in an [Arc](std::sync::Arc).
 pub struct ImmutableSearchEngine<Value, Query: ?Sized> {
-    engine: Box<dyn ImmutableSearchEngineTrait<Value, Query>>,
+    engine: Box<dyn ImmutableSearchEngineTrait<Value, Query> + Send + Sync>,
 }
 
 impl<Value, Query: ?Sized> ImmutableSearchEngine<Value, Query> {
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
     pub fn similarities(&self, query: &Query) -> Vec<(&Value, f64)> {
         self.engine.similarities_wrapper(query)
     }
 
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
     pub fn search(&self, query: &Query) -> Vec<&Value> {
         self.engine.search_wrapper(query)
     }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the most similar value first. \
+    /// This is identical to [similarities](Self::similarities), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_descending(&self, query: &Query) -> Vec<(&Value, f64)> {
+        self.engine.similarities_descending_wrapper(query)
+    }
+
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first. \
+    /// This is identical to [search](Self::search), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_descending(&self, query: &Query) -> Vec<&Value> {
+        self.similarities_descending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_top_k(&self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.engine.similarities_top_k_wrapper(query, k)
+    }
+
+    /// Retrieves references to the `k` values most similar to the given query,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` references to the values ranked by their similarity to the query.
+    pub fn search_top_k(&self, query: &Query, k: usize) -> Vec<&Value> {
+        self.similarities_top_k(query, k)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, excluding all values with a score below `threshold` or a NaN score,
+    /// with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_above(&self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.engine.similarities_above_wrapper(query, threshold)
+    }
+
+    /// Retrieves a vector of references to the values, excluding all values with a score below `threshold`
+    /// or a NaN score, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_above(&self, query: &Query, threshold: f64) -> Vec<&Value> {
+        self.similarities_above(query, threshold)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
 }
 
 /// Wrapper struct for type erased search engines requiring mutable access due to being stateful.
@@ -138,13 +307,135 @@ pub struct MutableSearchEngine<Value, Query: ?Sized> {
 }
 
 impl<Value, Query: ?Sized> MutableSearchEngine<Value, Query> {
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
     pub fn similarities(&mut self, query: &Query) -> Vec<(&Value, f64)> {
         self.engine.similarities_wrapper(query)
     }
 
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
     pub fn search(&mut self, query: &Query) -> Vec<&Value> {
         self.engine.search_wrapper(query)
     }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the most similar value first. \
+    /// This is identical to [similarities](Self::similarities), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_descending(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        self.engine.similarities_descending_wrapper(query)
+    }
+
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first. \
+    /// This is identical to [search](Self::search), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_descending(&mut self, query: &Query) -> Vec<&Value> {
+        self.similarities_descending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_top_k(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.engine.similarities_top_k_wrapper(query, k)
+    }
+
+    /// Retrieves references to the `k` values most similar to the given query,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` references to the values ranked by their similarity to the query.
+    pub fn search_top_k(&mut self, query: &Query, k: usize) -> Vec<&Value> {
+        self.similarities_top_k(query, k)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, excluding all values with a score below `threshold` or a NaN score,
+    /// with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_above(&mut self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.engine.similarities_above_wrapper(query, threshold)
+    }
+
+    /// Retrieves a vector of references to the values, excluding all values with a score below `threshold`
+    /// or a NaN score, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_above(&mut self, query: &Query, threshold: f64) -> Vec<&Value> {
+        self.similarities_above(query, threshold)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
 }
 
 impl<Value, Query: ?Sized> Clone for MutableSearchEngine<Value, Query> {
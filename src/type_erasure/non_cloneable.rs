@@ -8,7 +8,13 @@ where
     Value: 'static,
     Query: 'static,
     S: Similarity<Value, Query, State = ()> + 'static,
+    Self: Send + Sync,
 {
+    /// Erases the type of an engine, so engines with different similarity functions can be stored together.
+    ///
+    /// # Returns
+    ///
+    /// Returns a wrapper around the engine, that only depends on the types of the values and the query.
     pub fn erase_type(self) -> ImmutableSearchEngine<Value, Query> {
         ImmutableSearchEngine {
             engine: Box::new(self),
@@ -22,6 +28,11 @@ where
     Query: 'static,
     S: Similarity<Value, Query> + 'static,
 {
+    /// Erases the type of an engine, so engines with different similarity functions can be stored together.
+    ///
+    /// # Returns
+    ///
+    /// Returns a wrapper around the engine, that only depends on the types of the values and the query.
     pub fn erase_type(self) -> MutableSearchEngine<Value, Query> {
         MutableSearchEngine {
             engine: Box::new(self),
@@ -29,16 +40,57 @@ where
     }
 }
 
+impl<'a, Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Immutable>
+where
+    Value: 'a,
+    Query: 'a,
+    S: Similarity<Value, Query, State = ()> + 'a,
+    Self: Send + Sync,
+{
+    /// Erases the type of an engine, that may borrow data for the lifetime `'a`.
+    pub fn erase_type_ref(self) -> ImmutableSearchEngineRef<'a, Value, Query> {
+        ImmutableSearchEngineRef {
+            engine: Box::new(self),
+        }
+    }
+}
+
+impl<'a, Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Mutable>
+where
+    Value: 'a,
+    Query: 'a,
+    S: Similarity<Value, Query> + 'a,
+{
+    /// Erases the type of an engine, that may borrow data for the lifetime `'a`.
+    pub fn erase_type_ref(self) -> MutableSearchEngineRef<'a, Value, Query> {
+        MutableSearchEngineRef {
+            engine: Box::new(self),
+        }
+    }
+}
+
 trait ImmutableSearchEngineTrait<Value, Query: ?Sized> {
     fn similarities_wrapper(&self, query: &Query) -> Vec<(&Value, f64)>;
 
     fn search_wrapper(&self, query: &Query) -> Vec<&Value>;
+
+    fn similarities_descending_wrapper(&self, query: &Query) -> Vec<(&Value, f64)>;
+
+    fn similarities_top_k_wrapper(&self, query: &Query, k: usize) -> Vec<(&Value, f64)>;
+
+    fn similarities_above_wrapper(&self, query: &Query, threshold: f64) -> Vec<(&Value, f64)>;
 }
 
 trait MutableSearchEngineTrait<Value, Query: ?Sized> {
     fn similarities_wrapper(&mut self, query: &Query) -> Vec<(&Value, f64)>;
 
     fn search_wrapper(&mut self, query: &Query) -> Vec<&Value>;
+
+    fn similarities_descending_wrapper(&mut self, query: &Query) -> Vec<(&Value, f64)>;
+
+    fn similarities_top_k_wrapper(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)>;
+
+    fn similarities_above_wrapper(&mut self, query: &Query, threshold: f64) -> Vec<(&Value, f64)>;
 }
 
 impl<Value, Query: ?Sized, S> ImmutableSearchEngineTrait<Value, Query>
@@ -53,6 +105,18 @@ where
     fn search_wrapper(&self, query: &Query) -> Vec<&Value> {
         <SearchEngine<Value, Query, S, Immutable>>::search(self, query)
     }
+
+    fn similarities_descending_wrapper(&self, query: &Query) -> Vec<(&Value, f64)> {
+        self.similarities_descending(query)
+    }
+
+    fn similarities_top_k_wrapper(&self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.similarities_top_k(query, k)
+    }
+
+    fn similarities_above_wrapper(&self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.similarities_above(query, threshold)
+    }
 }
 
 impl<Value, Query: ?Sized, S> MutableSearchEngineTrait<Value, Query>
@@ -67,21 +131,156 @@ where
     fn search_wrapper(&mut self, query: &Query) -> Vec<&Value> {
         self.search(query)
     }
+
+    fn similarities_descending_wrapper(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        self.similarities_descending(query)
+    }
+
+    fn similarities_top_k_wrapper(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.similarities_top_k(query, k)
+    }
+
+    fn similarities_above_wrapper(&mut self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.similarities_above(query, threshold)
+    }
 }
 
-/// Wrapper struct for type erased search engines not requiring mutable access due to being stateless.
+/// Wrapper struct for type erased search engines not requiring mutable access due to being stateless. \
+/// It is [Send] + [Sync], so it can be queried concurrently from multiple threads, e.g. by wrapping it in an [Arc](std::sync::Arc).
 pub struct ImmutableSearchEngine<Value, Query: ?Sized> {
-    engine: Box<dyn ImmutableSearchEngineTrait<Value, Query>>,
+    engine: Box<dyn ImmutableSearchEngineTrait<Value, Query> + Send + Sync>,
 }
 
 impl<Value, Query: ?Sized> ImmutableSearchEngine<Value, Query> {
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
     pub fn similarities(&self, query: &Query) -> Vec<(&Value, f64)> {
         self.engine.similarities_wrapper(query)
     }
 
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
     pub fn search(&self, query: &Query) -> Vec<&Value> {
         self.engine.search_wrapper(query)
     }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the most similar value first. \
+    /// This is identical to [similarities](Self::similarities), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_descending(&self, query: &Query) -> Vec<(&Value, f64)> {
+        self.engine.similarities_descending_wrapper(query)
+    }
+
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first. \
+    /// This is identical to [search](Self::search), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_descending(&self, query: &Query) -> Vec<&Value> {
+        self.similarities_descending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_top_k(&self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.engine.similarities_top_k_wrapper(query, k)
+    }
+
+    /// Retrieves references to the `k` values most similar to the given query,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` references to the values ranked by their similarity to the query.
+    pub fn search_top_k(&self, query: &Query, k: usize) -> Vec<&Value> {
+        self.similarities_top_k(query, k)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, excluding all values with a score below `threshold` or a NaN score,
+    /// with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_above(&self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.engine.similarities_above_wrapper(query, threshold)
+    }
+
+    /// Retrieves a vector of references to the values, excluding all values with a score below `threshold`
+    /// or a NaN score, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_above(&self, query: &Query, threshold: f64) -> Vec<&Value> {
+        self.similarities_above(query, threshold)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
 }
 
 /// Wrapper struct for type erased search engines requiring mutable access due to being stateful.
@@ -90,11 +289,409 @@ pub struct MutableSearchEngine<Value, Query: ?Sized> {
 }
 
 impl<Value, Query: ?Sized> MutableSearchEngine<Value, Query> {
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        self.engine.similarities_wrapper(query)
+    }
+
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search(&mut self, query: &Query) -> Vec<&Value> {
+        self.engine.search_wrapper(query)
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the most similar value first. \
+    /// This is identical to [similarities](Self::similarities), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_descending(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        self.engine.similarities_descending_wrapper(query)
+    }
+
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first. \
+    /// This is identical to [search](Self::search), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_descending(&mut self, query: &Query) -> Vec<&Value> {
+        self.similarities_descending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_top_k(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.engine.similarities_top_k_wrapper(query, k)
+    }
+
+    /// Retrieves references to the `k` values most similar to the given query,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` references to the values ranked by their similarity to the query.
+    pub fn search_top_k(&mut self, query: &Query, k: usize) -> Vec<&Value> {
+        self.similarities_top_k(query, k)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, excluding all values with a score below `threshold` or a NaN score,
+    /// with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_above(&mut self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.engine.similarities_above_wrapper(query, threshold)
+    }
+
+    /// Retrieves a vector of references to the values, excluding all values with a score below `threshold`
+    /// or a NaN score, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_above(&mut self, query: &Query, threshold: f64) -> Vec<&Value> {
+        self.similarities_above(query, threshold)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+}
+
+/// Wrapper struct for type erased search engines not requiring mutable access due to being stateless,
+/// that may borrow data for the lifetime `'a`.
+pub struct ImmutableSearchEngineRef<'a, Value, Query: ?Sized> {
+    engine: Box<dyn ImmutableSearchEngineTrait<Value, Query> + Send + Sync + 'a>,
+}
+
+impl<Value, Query: ?Sized> ImmutableSearchEngineRef<'_, Value, Query> {
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities(&self, query: &Query) -> Vec<(&Value, f64)> {
+        self.engine.similarities_wrapper(query)
+    }
+
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search(&self, query: &Query) -> Vec<&Value> {
+        self.engine.search_wrapper(query)
+    }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the most similar value first. \
+    /// This is identical to [similarities](Self::similarities), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_descending(&self, query: &Query) -> Vec<(&Value, f64)> {
+        self.engine.similarities_descending_wrapper(query)
+    }
+
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first. \
+    /// This is identical to [search](Self::search), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_descending(&self, query: &Query) -> Vec<&Value> {
+        self.similarities_descending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_top_k(&self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.engine.similarities_top_k_wrapper(query, k)
+    }
+
+    /// Retrieves references to the `k` values most similar to the given query,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` references to the values ranked by their similarity to the query.
+    pub fn search_top_k(&self, query: &Query, k: usize) -> Vec<&Value> {
+        self.similarities_top_k(query, k)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, excluding all values with a score below `threshold` or a NaN score,
+    /// with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_above(&self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.engine.similarities_above_wrapper(query, threshold)
+    }
+
+    /// Retrieves a vector of references to the values, excluding all values with a score below `threshold`
+    /// or a NaN score, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_above(&self, query: &Query, threshold: f64) -> Vec<&Value> {
+        self.similarities_above(query, threshold)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+}
+
+/// Wrapper struct for type erased search engines requiring mutable access due to being stateful,
+/// that may borrow data for the lifetime `'a`.
+pub struct MutableSearchEngineRef<'a, Value, Query: ?Sized> {
+    engine: Box<dyn MutableSearchEngineTrait<Value, Query> + 'a>,
+}
+
+impl<Value, Query: ?Sized> MutableSearchEngineRef<'_, Value, Query> {
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
     pub fn similarities(&mut self, query: &Query) -> Vec<(&Value, f64)> {
         self.engine.similarities_wrapper(query)
     }
 
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
     pub fn search(&mut self, query: &Query) -> Vec<&Value> {
         self.engine.search_wrapper(query)
     }
+
+    /// Retrieves a vector of tuples containing references to the values and their similarity scores
+    /// to the given query, sorted with the most similar value first. \
+    /// This is identical to [similarities](Self::similarities), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_descending(&mut self, query: &Query) -> Vec<(&Value, f64)> {
+        self.engine.similarities_descending_wrapper(query)
+    }
+
+    /// Retrieves a vector of references to the values, sorted with the most similar value to the given query first. \
+    /// This is identical to [search](Self::search), which uses the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_descending(&mut self, query: &Query) -> Vec<&Value> {
+        self.similarities_descending(query)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves the `k` values most similar to the given query together with their similarity scores,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_top_k(&mut self, query: &Query, k: usize) -> Vec<(&Value, f64)> {
+        self.engine.similarities_top_k_wrapper(query, k)
+    }
+
+    /// Retrieves references to the `k` values most similar to the given query,
+    /// sorted with the most similar value first. Values with a NaN score are excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `k` - The maximum number of values to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of at most `k` references to the values ranked by their similarity to the query.
+    pub fn search_top_k(&mut self, query: &Query, k: usize) -> Vec<&Value> {
+        self.similarities_top_k(query, k)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
+
+    /// Retrieves a sorted vector of tuples containing references to the values and their similarity scores
+    /// to the given query, excluding all values with a score below `threshold` or a NaN score,
+    /// with the most similar value first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples where the first element is a reference to a value and the second element
+    /// is its similarity score as a floating-point number.
+    pub fn similarities_above(&mut self, query: &Query, threshold: f64) -> Vec<(&Value, f64)> {
+        self.engine.similarities_above_wrapper(query, threshold)
+    }
+
+    /// Retrieves a vector of references to the values, excluding all values with a score below `threshold`
+    /// or a NaN score, sorted with the most similar value to the given query first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query against which to rank the values.
+    /// * `threshold` - The minimum similarity score of a returned value.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of references to the values ranked by their similarity to the query.
+    pub fn search_above(&mut self, query: &Query, threshold: f64) -> Vec<&Value> {
+        self.similarities_above(query, threshold)
+            .into_iter()
+            .map(|v| v.0)
+            .collect()
+    }
 }
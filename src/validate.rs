@@ -0,0 +1,175 @@
+//! This module provides a validation of the weights of a [`SearchEngine`],
+//! detecting similarity functions that never determine the similarity of a value.
+
+use std::fmt::{Display, Formatter};
+
+use crate::search_engine::{CombineMode, Immutable, Mutable, SearchEngine};
+use crate::similarity::Similarity;
+
+/// A problem with the configuration of a search engine, as found by `validate`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// The weighted similarity of `layer` never exceeded the lowest weighted similarity of `dominated_by`,
+    /// so `layer` can never win the maximum and has no effect on the results. \
+    /// This is only reported for layers combined with the [maximum](CombineMode::Max),
+    /// as under the other modes every layer changes the result.
+    Dominated {
+        /// The dominated layer, where layer 0 is the first similarity function added.
+        layer: usize,
+        /// The layer always scoring at least as high.
+        dominated_by: usize,
+        /// The highest weighted similarity observed for `layer`.
+        max: f64,
+        /// The lowest weighted similarity observed for `dominated_by`.
+        min: f64,
+    },
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::Dominated {
+                layer,
+                dominated_by,
+                max,
+                min,
+            } => write!(
+                f,
+                "layer {layer} scores at most {max}, which never exceeds the minimum {min} of layer {dominated_by}"
+            ),
+        }
+    }
+}
+
+/// Tracks the lowest and highest weighted similarity of every layer.
+struct LayerBounds {
+    bounds: Vec<(f64, f64)>,
+    /// The number of leading layers, whose combined similarity is their maximum.
+    max_layers: usize,
+}
+
+impl LayerBounds {
+    fn new<Value, Query: ?Sized, S>(similarity: &S) -> Self
+    where
+        S: Similarity<Value, Query>,
+    {
+        let layers = similarity.layers();
+        // The first layer is taken as is, every following one has to be folded in with the maximum.
+        let max_layers = (1..layers)
+            .find(|layer| similarity.combine_mode(*layer) != CombineMode::Max)
+            .unwrap_or(layers);
+        Self {
+            bounds: vec![(f64::INFINITY, f64::NEG_INFINITY); layers],
+            max_layers,
+        }
+    }
+
+    fn add(&mut self, similarities: &[f64]) {
+        for ((min, max), similarity) in self.bounds.iter_mut().zip(similarities) {
+            *min = min.min(*similarity);
+            *max = max.max(*similarity);
+        }
+    }
+
+    fn warnings(&self) -> Vec<Warning> {
+        // Without sample queries or values the bounds stay empty, and nothing can be concluded.
+        let observed = |(min, max): &(f64, f64)| min <= max;
+        // A layer combined with another mode changes the result, even if it scores lower.
+        let bounds = &self.bounds[..self.max_layers];
+        bounds
+            .iter()
+            .enumerate()
+            .filter(|(_, bounds)| observed(bounds))
+            .filter_map(|(layer, (layer_min, max))| {
+                bounds
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, bounds)| observed(bounds))
+                    // Two constant layers of the same similarity tie, so neither one dominates the other.
+                    .find(|(other, (min, other_max))| {
+                        *other != layer && max <= min && other_max > layer_min
+                    })
+                    .map(|(dominated_by, (min, _))| Warning::Dominated {
+                        layer,
+                        dominated_by,
+                        max: *max,
+                        min: *min,
+                    })
+            })
+            .collect()
+    }
+}
+
+impl<Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Mutable>
+where
+    S: Similarity<Value, Query>,
+{
+    /// Scores all values against the sample queries layer by layer, and reports every layer whose
+    /// weighted similarity never exceeded the lowest weighted similarity of another layer. \
+    /// Combined with the default [maximum](crate::search_engine::CombineMode::Max), such a layer never changes the results,
+    /// which usually means its weight is too low. Layers combined with another mode are never reported,
+    /// neither are the layers after them. The check is only as good as the sample queries. \
+    /// This version of the function is used, if the search engine contains stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - Representative queries to score the values against.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of warnings, which is empty if no problems were found.
+    pub fn validate(&mut self, queries: &[&Query]) -> Vec<Warning> {
+        let mut bounds = LayerBounds::new(self.similarity());
+        let mut similarities = Vec::new();
+        for query in queries {
+            let (similarity, values) = self.similarity_and_values_mut();
+            let query_state = similarity.query_state(query);
+            for (state, value) in values {
+                similarities.clear();
+                similarity.layer_similarities(state, &query_state, value, query, &mut similarities);
+                bounds.add(&similarities);
+            }
+        }
+        bounds.warnings()
+    }
+}
+
+impl<Value, Query: ?Sized, S> SearchEngine<Value, Query, S, Immutable>
+where
+    S: Similarity<Value, Query, State = ()>,
+{
+    /// Scores all values against the sample queries layer by layer, and reports every layer whose
+    /// weighted similarity never exceeded the lowest weighted similarity of another layer. \
+    /// Combined with the default [maximum](crate::search_engine::CombineMode::Max), such a layer never changes the results,
+    /// which usually means its weight is too low. Layers combined with another mode are never reported,
+    /// neither are the layers after them. The check is only as good as the sample queries. \
+    /// This version of the function is used, if the search engine contains no stateful functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - Representative queries to score the values against.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of warnings, which is empty if no problems were found.
+    pub fn validate(&self, queries: &[&Query]) -> Vec<Warning> {
+        let similarity = self.similarity();
+        let mut bounds = LayerBounds::new(similarity);
+        let mut similarities = Vec::new();
+        for query in queries {
+            let query_state = similarity.query_state(query);
+            for value in self.values() {
+                similarities.clear();
+                similarity.layer_similarities(
+                    &mut (),
+                    &query_state,
+                    value,
+                    query,
+                    &mut similarities,
+                );
+                bounds.add(&similarities);
+            }
+        }
+        bounds.warnings()
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use rand::distributions::{Alphanumeric, DistString};
+    use rand::prelude::*;
+    use simple_search::automaton::LevenshteinAutomaton;
+    use simple_search::levenshtein::base::bounded_levenshtein_distance;
+    use simple_search::search_engine::SearchEngine;
+
+    #[test]
+    fn test_automaton_agrees_with_bounded() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..200 {
+            let query_len = rng.gen_range(0..=8);
+            let query = Alphanumeric
+                .sample_string(&mut rng, query_len)
+                .to_lowercase();
+            let value_len = rng.gen_range(0..=10);
+            let value = Alphanumeric
+                .sample_string(&mut rng, value_len)
+                .to_lowercase();
+
+            for max_distance in 0..=6 {
+                let automaton = LevenshteinAutomaton::new(&query, max_distance);
+                assert_eq!(
+                    automaton.matches(&value),
+                    bounded_levenshtein_distance(&query, &value, max_distance),
+                    "query: {query}, value: {value}, max: {max_distance}"
+                );
+            }
+        }
+
+        let automaton = LevenshteinAutomaton::new("café", 1);
+        assert_eq!(automaton.matches("cafe"), Some(1));
+        assert_eq!(automaton.matches("café"), Some(0));
+        assert_eq!(automaton.matches("coffee"), None);
+    }
+
+    #[test]
+    fn test_search_with_automaton() {
+        let engine = SearchEngine::new().with_values(vec!["hello", "hallo", "help", "world"]);
+
+        let results = engine.search_with_automaton("hello", 1);
+        assert_eq!(results, vec![(&"hello", 0), (&"hallo", 1)]);
+    }
+}
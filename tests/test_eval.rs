@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::eval::{mrr, ndcg_at_k};
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_mrr() {
+        let relevant = HashSet::from(["gatsby"]);
+
+        assert_eq!(mrr(&["gatsby", "winter", "world"], &relevant), 1.);
+        assert_eq!(mrr(&["winter", "world", "gatsby"], &relevant), 1. / 3.);
+        assert_eq!(mrr(&["winter", "world"], &relevant), 0.);
+        assert_eq!(mrr(&[], &relevant), 0.);
+
+        let relevant = HashSet::from(["world", "gatsby"]);
+        assert_eq!(mrr(&["winter", "world", "gatsby"], &relevant), 0.5);
+    }
+
+    #[test]
+    fn test_ndcg_at_k() {
+        let relevance = HashMap::from([("a", 3.), ("b", 2.), ("c", 1.)]);
+
+        assert_eq!(ndcg_at_k(&["a", "b", "c"], &relevance, 3), 1.);
+        assert_eq!(ndcg_at_k(&["a", "b", "x", "c"], &relevance, 2), 1.);
+
+        // dcg = 1 + 2 / log2(3) + 3 / 2, ideal = 3 + 2 / log2(3) + 1 / 2
+        let expected = (1. + 2. / 3f64.log2() + 1.5) / (3. + 2. / 3f64.log2() + 0.5);
+        let ndcg = ndcg_at_k(&["c", "b", "a"], &relevance, 3);
+        assert!((ndcg - expected).abs() < 1e-12);
+
+        // Only "b" is ranked in the top 2: 2 / log2(3) against the ideal 3 + 2 / log2(3).
+        let expected = (2. / 3f64.log2()) / (3. + 2. / 3f64.log2());
+        let ndcg = ndcg_at_k(&["x", "b", "a"], &relevance, 2);
+        assert!((ndcg - expected).abs() < 1e-12);
+
+        assert_eq!(ndcg_at_k(&["x", "y"], &relevance, 2), 0.);
+        assert_eq!(ndcg_at_k(&["a"], &HashMap::new(), 1), 0.);
+        assert!(
+            ndcg_at_k(&["a", "c", "b"], &relevance, 3) > ndcg_at_k(&["c", "a", "b"], &relevance, 3)
+        );
+    }
+}
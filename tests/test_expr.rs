@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::expr::Expr;
+    use simple_search::levenshtein::base::normalized_levenshtein_similarity;
+    use simple_search::search_engine::SearchEngine;
+
+    type Book = (&'static str, &'static str, &'static str);
+
+    fn contains(word: &'static str) -> Expr<'static, Book, str> {
+        Expr::new(move |book: &Book, _: &str| if book.2.contains(word) { 1. } else { 0. })
+    }
+
+    #[test]
+    fn test_and() {
+        let books: Vec<Book> = vec![
+            ("Ulysses", "Joyce", "a day in dublin"),
+            ("Dubliners", "Joyce", "short stories"),
+            ("Dubliners", "Unknown", "a guide to dublin"),
+        ];
+
+        let title = Expr::new(|b: &Book, q: &str| normalized_levenshtein_similarity(q, b.0));
+        let author = Expr::new(|b: &Book, _: &str| normalized_levenshtein_similarity("Joyce", b.1));
+
+        let engine = SearchEngine::new()
+            .with_values(books.clone())
+            .with_expr(title.and(author));
+
+        let results = engine.similarities("Dubliners");
+        assert_eq!(results[0], (&books[1], 1.));
+        assert!(results[1].1 < 1.);
+    }
+
+    #[test]
+    fn test_or_not() {
+        let book: Book = ("Ulysses", "Joyce", "a day in dublin");
+
+        assert_eq!(
+            contains("dublin").or(contains("paris")).evaluate(&book, ""),
+            1.
+        );
+        assert_eq!(
+            contains("london").or(contains("paris")).evaluate(&book, ""),
+            0.
+        );
+        assert_eq!((!contains("dublin")).evaluate(&book, ""), 0.);
+        assert_eq!(
+            contains("day").and(!contains("paris")).evaluate(&book, ""),
+            1.
+        );
+    }
+}
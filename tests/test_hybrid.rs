@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::hybrid::HybridEngine;
+    use simple_search::levenshtein::base::normalized_levenshtein_similarity;
+    use simple_search::levenshtein::incremental::IncrementalLevenshtein;
+    use simple_search::search_engine::SearchEngine;
+
+    #[derive(Debug, PartialEq)]
+    struct Product {
+        id: String,
+        name: String,
+    }
+
+    fn products() -> Vec<Product> {
+        [
+            ("B-200", "Blue Chair"),
+            ("A-100", "Red Table"),
+            ("C-300", "Green Lamp"),
+        ]
+        .into_iter()
+        .map(|(id, name)| Product {
+            id: id.to_string(),
+            name: name.to_string(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_exact_hit() {
+        let engine = SearchEngine::new()
+            .with_values(products())
+            .with(|p: &Product, q: &str| normalized_levenshtein_similarity(q, &p.id));
+        let mut hybrid = HybridEngine::new(engine, |p: &Product| p.id.clone());
+
+        assert_eq!(hybrid.lookup_exact("A-100").unwrap().name, "Red Table");
+        assert_eq!(hybrid.lookup_exact("X-999"), None);
+
+        hybrid.add_value(Product {
+            id: "A-100".to_string(),
+            name: "Red Table (used)".to_string(),
+        });
+        let results = hybrid.search_exact_or_fuzzy("A-100");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "Red Table");
+        assert_eq!(results[1].name, "Red Table (used)");
+    }
+
+    #[test]
+    fn test_fuzzy_fallback() {
+        let engine = SearchEngine::new().with_values(products()).with_state(
+            |p: &Product| IncrementalLevenshtein::new("", &p.id),
+            |s, _, q: &str| s.weighted_similarity(q),
+        );
+        let mut hybrid = HybridEngine::new(engine, |p: &Product| p.id.clone());
+
+        let results = hybrid.search_exact_or_fuzzy("C-30");
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].id, "C-300");
+
+        let results = hybrid.search_exact_or_fuzzy("C-300");
+        assert_eq!(results.len(), 1);
+    }
+}
@@ -9,12 +9,20 @@ mod tests {
     use crate::print_matrix;
     use rand::distributions::{Alphanumeric, DistString};
     use rand::prelude::*;
-    use simple_search::levenshtein::base::{levenshtein_matrix, weighted_levenshtein_similarity};
+    use simple_search::levenshtein::base::{
+        levenshtein_matrix, levenshtein_similarity, weighted_levenshtein_similarity,
+    };
     use simple_search::levenshtein::incremental::IncrementalLevenshtein;
-    use simple_search::search_engine::SearchEngine;
+    use simple_search::search_engine::{SearchEngine, SearchResult};
+    use simple_search::test_utils::assert_rankings_equivalent;
     use std::collections::HashMap;
 
     #[test]
+    #[allow(
+        clippy::clone_on_copy,
+        clippy::into_iter_on_ref,
+        clippy::needless_borrow
+    )]
     fn test_incremental() {
         let mut rng = StdRng::seed_from_u64(42);
 
@@ -95,4 +103,216 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_weighted_orientation() {
+        let pairs = [
+            ("hallo", "hello world"),
+            ("Fitzereld", "F. Scott Fitzgerald"),
+            ("water and fire", "Fire adn water"),
+            ("", "data"),
+            ("query", ""),
+        ];
+
+        for (query, data) in pairs {
+            let mut incremental = IncrementalLevenshtein::new("", data);
+            assert_eq!(
+                incremental.weighted_similarity(query),
+                weighted_levenshtein_similarity(query, data)
+            );
+        }
+    }
+
+    #[test]
+    fn test_search_result_eq() {
+        let data = vec!["hello world", "help", "hollow", "yellow", "word"];
+
+        let regular = SearchEngine::new()
+            .with_values(data.clone())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+
+        let mut incremental = SearchEngine::new().with_values(data).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+
+        for query in ["h", "he", "hel", "hell", "hello"] {
+            assert_rankings_equivalent(
+                &regular.similarities(query),
+                &incremental.similarities(query),
+                1e-9,
+            );
+        }
+
+        assert_eq!(
+            SearchResult::new("a", 0.1 + 0.2),
+            SearchResult::new("a", 0.3)
+        );
+        assert_ne!(SearchResult::new("a", 0.3), SearchResult::new("b", 0.3));
+        assert!(SearchResult::new("a", 0.3).approx_eq(&SearchResult::new("a", 0.35), 0.1));
+    }
+
+    #[test]
+    fn test_case_insensitive_updates() {
+        let mut incremental = IncrementalLevenshtein::new_case_insensitive("", "ca");
+        for query in ["Ca", "cA", "CA", "c", "CA"] {
+            let expected = if query.len() == 2 { 1. } else { 0.5 };
+            assert_eq!(incremental.similarity(query), expected);
+            assert_eq!(
+                incremental.weighted_similarity(query),
+                weighted_levenshtein_similarity(&query.to_lowercase(), "ca")
+            );
+        }
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut incremental = IncrementalLevenshtein::new("", "hello world");
+        assert_eq!(incremental.matrix_cells(), 12);
+
+        incremental.similarity("hello");
+        assert_eq!(incremental.matrix_cells(), 6 * 12);
+
+        incremental.compact();
+        assert_eq!(incremental.matrix_cells(), 12);
+        assert_eq!(incremental.query(), "");
+
+        for query in ["hallo", "world", ""] {
+            assert_eq!(
+                incremental.weighted_similarity(query),
+                weighted_levenshtein_similarity(query, "hello world")
+            );
+            assert_eq!(
+                incremental.similarity(query),
+                IncrementalLevenshtein::new("", "hello world").similarity(query)
+            );
+        }
+    }
+
+    #[test]
+    fn test_shared_suffix() {
+        let suffix = "the great gatsby by f. scott fitzgerald";
+        let data = format!("read {suffix}");
+
+        let mut incremental = IncrementalLevenshtein::new("", &data);
+        for query in [
+            format!("red {suffix}"),
+            format!("rad {suffix}"),
+            format!("reed {suffix}"),
+            format!("write {suffix}"),
+        ] {
+            let similarity = incremental.similarity(&query);
+            // Only the rows of the edited prefix are recomputed, not the shared suffix.
+            assert!(incremental.recomputed_cells() <= 5 * data.len());
+
+            let matrix = levenshtein_matrix(&query, &data);
+            let max_distance = query.len().max(data.len());
+            let distance = matrix[query.len()][data.len()];
+            assert_eq!(
+                similarity,
+                (max_distance - distance) as f64 / max_distance as f64
+            );
+            assert_eq!(
+                similarity,
+                IncrementalLevenshtein::new("", &data).similarity(&query)
+            );
+            assert_eq!(
+                incremental.weighted_similarity(&query),
+                weighted_levenshtein_similarity(&query, &data)
+            );
+        }
+    }
+
+    #[test]
+    fn test_reversed() {
+        let data = "the great gatsby";
+        let mut reversed = IncrementalLevenshtein::new_reversed("", data);
+
+        let mut query = String::new();
+        for c in "great gatsbee".chars().rev() {
+            query.insert(0, c);
+
+            assert_eq!(
+                reversed.similarity(&query),
+                IncrementalLevenshtein::new("", data).similarity(&query)
+            );
+            // Only the row of the prepended char is recomputed,
+            // plus the row of a shared first char, which the previous query could skip.
+            assert!(reversed.recomputed_cells() <= 2 * data.len());
+        }
+
+        assert_eq!(
+            reversed.weighted_similarity(&query),
+            weighted_levenshtein_similarity(&query, data)
+        );
+        assert_eq!(
+            reversed.weighted_similarity("gatsby the great"),
+            weighted_levenshtein_similarity("gatsby the great", data)
+        );
+    }
+
+    #[test]
+    fn test_similarities_pooled() {
+        let engine = SearchEngine::new()
+            .with_values(vec!["hello", "world", "help", "hollow"])
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+
+        let mut pool = engine.new_pool();
+        for query in ["hel", "hello", "wor"] {
+            for (value, similarity) in engine.similarities_pooled(query, &mut pool) {
+                assert_eq!(similarity, weighted_levenshtein_similarity(query, value));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_similarities_pooled_length_mismatch() {
+        let engine = SearchEngine::new()
+            .with_values(vec!["hello", "world"])
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+
+        let mut pool = engine.new_pool();
+        pool.pop();
+        engine.similarities_pooled("hello", &mut pool);
+    }
+
+    #[test]
+    fn test_query_and_data() {
+        let mut incremental = IncrementalLevenshtein::new("", "Hello");
+        assert_eq!(incremental.query(), "");
+        incremental.similarity("Hel");
+        assert_eq!(incremental.query(), "Hel");
+        assert_eq!(incremental.data(), "Hello");
+        assert_eq!(incremental.matrix().len(), 4);
+
+        let mut incremental = IncrementalLevenshtein::new_case_insensitive("", "Hello");
+        incremental.similarity("HeL");
+        assert_eq!(incremental.query(), "hel");
+        assert_eq!(incremental.data(), "hello");
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let mut incremental = IncrementalLevenshtein::new("", "Grüße aus Köln");
+        for query in [
+            "G",
+            "Grü",
+            "Grüß",
+            "Grüße",
+            "Grüße aus Koln",
+            "Gruße",
+            "\u{1F44D}",
+        ] {
+            assert_eq!(
+                incremental.similarity(query),
+                levenshtein_similarity(query, "Grüße aus Köln")
+            );
+            assert_eq!(
+                incremental.weighted_similarity(query),
+                weighted_levenshtein_similarity(query, "Grüße aus Köln")
+            );
+        }
+        assert_eq!(incremental.matrix().len(), 2);
+    }
 }
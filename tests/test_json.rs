@@ -0,0 +1,43 @@
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{json, Value};
+    use simple_search::json::{json_text, json_text_at};
+    use simple_search::search_engine::SearchEngine;
+
+    fn books() -> Vec<Value> {
+        vec![
+            json!({"title": "Brave New World", "author": {"name": "Aldous Huxley", "born": 1894}}),
+            json!({"title": "The Great Gatsby", "author": {"name": "F. Scott Fitzgerald"}}),
+            json!({"title": "Ulysses", "tags": ["modernist", "irish"], "author": {"name": "James Joyce"}}),
+        ]
+    }
+
+    #[test]
+    fn test_json_text() {
+        let book = &books()[0];
+        assert_eq!(json_text(book), "Aldous Huxley Brave New World");
+        assert_eq!(json_text_at(book, &["/title"]), "Brave New World");
+        assert_eq!(
+            json_text_at(book, &["/author/name", "/missing"]),
+            "Aldous Huxley"
+        );
+        assert_eq!(json_text(&json!([1, null, true])), "");
+    }
+
+    #[test]
+    fn test_json_search() {
+        let engine = SearchEngine::new().with_values(books()).with_json();
+        let results = engine.search("Fitzgerald");
+        assert_eq!(results[0]["title"], "The Great Gatsby");
+
+        let results = engine.search("irish");
+        assert_eq!(results[0]["title"], "Ulysses");
+
+        // Restricted to the titles, the author of "The Great Gatsby" no longer matches.
+        let titles = SearchEngine::new()
+            .with_values(books())
+            .with_json_fields(&["/title"]);
+        let similarities = titles.similarities("Fitzgerald");
+        assert!(similarities.iter().all(|(_, similarity)| *similarity < 0.5));
+    }
+}
@@ -0,0 +1,489 @@
+#[cfg(test)]
+mod tests {
+    use rand::distributions::{Alphanumeric, DistString};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use simple_search::levenshtein::alignment::{align, Span, SpanKind};
+    use simple_search::levenshtein::base::{
+        bounded_levenshtein_distance, byte_levenshtein_similarity, common_prefix, common_suffix,
+        damerau_levenshtein_distance, damerau_levenshtein_similarity, hamming_distance,
+        hamming_similarity, levenshtein_distance, levenshtein_distance_linear, levenshtein_matrix,
+        levenshtein_report, levenshtein_similarity, levenshtein_similarity_normalized,
+        normalized_levenshtein_similarity, positional_levenshtein_similarity,
+        weighted_levenshtein_similarity, weighted_levenshtein_similarity_with,
+        whitespace_tolerant_similarity, NormBy,
+    };
+    use simple_search::levenshtein::incremental::IncrementalLevenshtein;
+    use simple_search::levenshtein::partial::{partial_match_span, partial_ratio};
+    use simple_search::search_engine::SearchEngine;
+
+    #[test]
+    fn test_byte_levenshtein() {
+        let values: Vec<Vec<u8>> = vec![
+            vec![0x00, 0xff, 0x10, 0x20],
+            vec![0xde, 0xad, 0xbe, 0xef],
+            vec![0xde, 0xad, 0xbe, 0x00],
+            vec![0x01, 0x02],
+        ];
+
+        let engine = SearchEngine::new()
+            .with_values(values)
+            .with(|v: &Vec<u8>, q: &[u8]| byte_levenshtein_similarity(v, q));
+
+        let results = engine.search(&[0xde, 0xad, 0xbe, 0xef]);
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0], &vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(results[1], &vec![0xde, 0xad, 0xbe, 0x00]);
+
+        assert_eq!(
+            byte_levenshtein_similarity(&[0xde, 0xad], &[0xde, 0xad]),
+            1.
+        );
+        assert_eq!(
+            byte_levenshtein_similarity(&[0xde, 0xad], &[0xbe, 0xef]),
+            0.
+        );
+    }
+
+    #[test]
+    fn test_normalized_levenshtein_symmetry() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("hello", "hallo"),
+            ("straße", "strasse"),
+            ("abc", ""),
+            ("flaw", "lawn"),
+        ];
+
+        for (a, b) in pairs {
+            assert_eq!(
+                normalized_levenshtein_similarity(a, b),
+                normalized_levenshtein_similarity(b, a)
+            );
+        }
+
+        assert_eq!(
+            normalized_levenshtein_similarity("kitten", "sitting"),
+            1. - 3. / 7.
+        );
+    }
+
+    #[test]
+    fn test_normalized_levenshtein_empty() {
+        assert_eq!(normalized_levenshtein_similarity("", ""), 1.);
+        assert_eq!(normalized_levenshtein_similarity("abc", ""), 0.);
+        assert_eq!(normalized_levenshtein_similarity("", "abc"), 0.);
+        assert_eq!(normalized_levenshtein_similarity("ä", ""), 0.);
+    }
+
+    #[test]
+    fn test_query_normalized_levenshtein() {
+        let query = "the grea";
+        let value = "The Great Gatsby";
+
+        let by_query = levenshtein_similarity_normalized(query, value, NormBy::Query);
+        let by_max = levenshtein_similarity_normalized(query, value, NormBy::Max);
+
+        assert_eq!(by_query, 6. / 8.);
+        assert_eq!(by_max, normalized_levenshtein_similarity(query, value));
+        assert!(by_query > by_max);
+
+        assert_eq!(
+            levenshtein_similarity_normalized("gatsby", "gatsby", NormBy::Query),
+            1.
+        );
+        assert_eq!(
+            levenshtein_similarity_normalized("", "abc", NormBy::Query),
+            1.
+        );
+        assert_eq!(
+            levenshtein_similarity_normalized("abc", "", NormBy::Query),
+            0.
+        );
+
+        // The query may match any substring of the value, not only its start.
+        let by_query = levenshtein_similarity_normalized("gat", "The Great Gatsby", NormBy::Query);
+        let by_max = levenshtein_similarity_normalized("gat", "The Great Gatsby", NormBy::Max);
+        assert_eq!(by_query, 2. / 3.);
+        assert!(by_max < 0.2);
+        assert_eq!(
+            levenshtein_similarity_normalized("gats", "the great gatsby", NormBy::Query),
+            1.
+        );
+        assert_eq!(
+            levenshtein_similarity_normalized("qxz", "The Great Gatsby", NormBy::Query),
+            0.
+        );
+    }
+
+    #[test]
+    fn test_exact_match_fast_path() {
+        for value in ["hello", "The Great Gatsby", "a"] {
+            assert_eq!(levenshtein_similarity(value, value), 1.);
+            assert_eq!(weighted_levenshtein_similarity(value, value), 1.);
+            assert_eq!(normalized_levenshtein_similarity(value, value), 1.);
+        }
+
+        assert_eq!(levenshtein_similarity("", ""), 0.);
+        assert_eq!(weighted_levenshtein_similarity("", ""), 0.);
+
+        // Near-matches still go through the matrix.
+        let matrix = levenshtein_matrix("hello", "hallo");
+        assert_eq!(matrix[5][5], 1);
+        assert_eq!(levenshtein_similarity("hello", "hallo"), 0.8);
+        assert!(weighted_levenshtein_similarity("hello", "hallo") < 1.);
+    }
+
+    #[test]
+    fn test_positional_levenshtein() {
+        let early = positional_levenshtein_similarity("xello world", "hello world", 0.8);
+        let late = positional_levenshtein_similarity("hello worlx", "hello world", 0.8);
+        assert!(early < late);
+
+        let early = positional_levenshtein_similarity("ello world", "hello world", 0.8);
+        let late = positional_levenshtein_similarity("hello worl", "hello world", 0.8);
+        assert!(early < late);
+
+        assert_eq!(positional_levenshtein_similarity("hello", "hello", 0.5), 1.);
+        assert_eq!(positional_levenshtein_similarity("", "", 0.5), 1.);
+        assert_eq!(positional_levenshtein_similarity("abc", "", 0.5), 0.);
+        assert_eq!(
+            positional_levenshtein_similarity("kitten", "sitting", 1.),
+            normalized_levenshtein_similarity("kitten", "sitting")
+        );
+    }
+
+    #[test]
+    fn test_levenshtein_report() {
+        for (a, b) in [
+            ("kitten", "sitting"),
+            ("hallo", "hello"),
+            ("same", "same"),
+            ("", "abc"),
+            ("", ""),
+        ] {
+            let report = levenshtein_report(a, b);
+            assert_eq!(report.distance, levenshtein_distance(a, b));
+            assert_eq!(report.similarity, levenshtein_similarity(a, b));
+            assert_eq!(
+                report.weighted_similarity,
+                weighted_levenshtein_similarity(a, b)
+            );
+        }
+    }
+
+    #[test]
+    fn test_whitespace_tolerant() {
+        assert_eq!(
+            whitespace_tolerant_similarity("TheGreatGatsby", "The Great Gatsby"),
+            1.
+        );
+        assert_eq!(
+            whitespace_tolerant_similarity("the  great\tgatsby ", "the great gatsby"),
+            1.
+        );
+        assert!(
+            weighted_levenshtein_similarity("TheGreatGatsby", "The Great Gatsby")
+                < whitespace_tolerant_similarity("TheGreatGatsby", "The Great Gatsby")
+        );
+
+        let typo = whitespace_tolerant_similarity("TheGraetGatsby", "The Great Gatsby");
+        assert!(typo < 1.);
+        assert!(typo > whitespace_tolerant_similarity("TheGraetGatsbee", "The Great Gatsby"));
+    }
+
+    #[test]
+    fn test_match_bonus() {
+        let query = "the great gatsby by fitzgeral";
+        let value = "the great gatsby by f. scott fitzgerald";
+
+        let with_bonus = weighted_levenshtein_similarity_with(query, value, true);
+        let without_bonus = weighted_levenshtein_similarity_with(query, value, false);
+        assert_eq!(without_bonus, weighted_levenshtein_similarity(query, value));
+
+        // The long matching run at the start earns a bonus.
+        assert!(with_bonus > without_bonus);
+        assert!(without_bonus <= 1.);
+        assert!(
+            weighted_levenshtein_similarity_with("fitzgerald", "fitzgerlad", true)
+                > weighted_levenshtein_similarity_with("fitzgerald", "fiztgerlad", true)
+        );
+
+        assert_eq!(
+            weighted_levenshtein_similarity_with(value, value, false),
+            1.
+        );
+        assert_eq!(weighted_levenshtein_similarity_with("", "", false), 0.);
+    }
+
+    #[test]
+    fn test_partial_match_span() {
+        let value = "The Great Gatsby";
+        let (range, similarity) = partial_match_span(value, "great");
+        assert_eq!(&value[range], "Great");
+        assert_eq!(similarity, 0.8);
+        assert_eq!(partial_ratio(value, "Gatsby"), 1.);
+
+        // The range lies on char boundaries of multi-byte chars.
+        let value = "Grüße aus Köln";
+        let (range, similarity) = partial_match_span(value, "Koln");
+        assert_eq!(&value[range], "Köln");
+        assert_eq!(similarity, 0.75);
+
+        let (range, similarity) = partial_match_span("ab", "abc");
+        assert_eq!(range, 0..2);
+        assert_eq!(similarity, normalized_levenshtein_similarity("abc", "ab"));
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn test_grapheme_levenshtein() {
+        use simple_search::levenshtein::base::{
+            grapheme_levenshtein_distance, grapheme_levenshtein_similarity,
+        };
+
+        let medium = "\u{1F44D}\u{1F3FD}";
+        let dark = "\u{1F44D}\u{1F3FF}";
+
+        assert_eq!(grapheme_levenshtein_similarity(medium, medium), 1.);
+        assert_eq!(grapheme_levenshtein_distance(medium, dark), 1);
+        assert_eq!(
+            grapheme_levenshtein_distance("great", "great\u{1F44D}\u{1F3FD}"),
+            1
+        );
+
+        // A combining accent belongs to its letter, so "e\u{301}" is a single edit away from "e".
+        let decomposed = "caf\u{65}\u{301}";
+        assert_eq!(grapheme_levenshtein_distance(decomposed, "cafe"), 1);
+        assert_eq!(grapheme_levenshtein_similarity(decomposed, "cafe"), 0.75);
+
+        // Chars split the emoji, so appending one costs two edits.
+        let with_emoji = format!("ok{medium}");
+        assert_eq!(grapheme_levenshtein_similarity("ok", &with_emoji), 2. / 3.);
+        assert_eq!(normalized_levenshtein_similarity("ok", &with_emoji), 0.5);
+
+        // Chars count the skin tone as a separate edit, while graphemes only see the whole emoji.
+        let tone_added = format!("great\u{1F44D}{medium}");
+        assert_eq!(
+            grapheme_levenshtein_similarity("great\u{1F44D}", &tone_added),
+            6. / 7.
+        );
+        assert_eq!(
+            normalized_levenshtein_similarity("great\u{1F44D}", &tone_added),
+            0.75
+        );
+        assert_eq!(
+            grapheme_levenshtein_similarity(&tone_added, &tone_added),
+            normalized_levenshtein_similarity(&tone_added, &tone_added)
+        );
+    }
+
+    #[test]
+    fn test_bounded_levenshtein() {
+        // All strings over a small alphabet with up to four chars.
+        let mut strings = vec![String::new()];
+        for len in 1..=4 {
+            let mut next = Vec::new();
+            for s in strings.iter().filter(|s| s.chars().count() == len - 1) {
+                for c in ['a', 'b', 'ü'] {
+                    next.push(format!("{s}{c}"));
+                }
+            }
+            strings.extend(next);
+        }
+
+        for a in &strings {
+            for b in &strings {
+                let distance = levenshtein_distance(a, b);
+                for max in 0..=5 {
+                    let expected = Some(distance).filter(|&d| d <= max);
+                    assert_eq!(bounded_levenshtein_distance(a, b, max), expected);
+                }
+            }
+        }
+
+        let long = "lorem ipsum dolor sit amet ".repeat(40);
+        let edited = long
+            .replacen("dolor", "dolar", 3)
+            .replacen("sit ", "st ", 1);
+        assert_eq!(bounded_levenshtein_distance(&long, &edited, 5), Some(4));
+        assert_eq!(bounded_levenshtein_distance(&long, &edited, 3), None);
+        assert_eq!(
+            bounded_levenshtein_distance(&long, &long, usize::MAX),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_linear_distance() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("sitting", "kitten"),
+            ("", "abc"),
+            ("abc", ""),
+            ("", ""),
+            ("grüße", "grusse"),
+            ("flaw", "lawn"),
+        ];
+        for (a, b) in pairs {
+            let matrix = levenshtein_matrix(a, b);
+            let expected = matrix[a.chars().count()][b.chars().count()];
+            assert_eq!(levenshtein_distance_linear(a, b), expected);
+            assert_eq!(levenshtein_distance(a, b), expected);
+        }
+    }
+
+    #[test]
+    fn test_linear_distance_long() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for len in [1000, 4000] {
+            let a = Alphanumeric.sample_string(&mut rng, len);
+            let b = Alphanumeric.sample_string(&mut rng, len);
+            assert_eq!(
+                levenshtein_distance_linear(&a, &b),
+                levenshtein_matrix(&a, &b)[len][len]
+            );
+        }
+    }
+
+    #[test]
+    fn test_common_suffix() {
+        assert_eq!(common_suffix("testing", "resting"), 6);
+        assert_eq!(common_suffix("abc", "xyz"), 0);
+        assert_eq!(common_suffix("", "abc"), 0);
+        assert_eq!(common_suffix("abc", "abc"), 3);
+
+        // Multibyte chars count as one char each, and a shared trailing byte isn't a shared char.
+        assert_eq!(common_suffix("Straße", "Grüße"), 2);
+        assert_eq!(common_suffix("née", "nee"), 1);
+        assert_eq!(common_suffix("caé", "caũ"), 0);
+        assert_eq!(common_suffix("naïve café", "naive café"), 7);
+        assert_eq!(common_prefix("café", "cafe"), 3);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        assert_eq!(damerau_levenshtein_distance("teh", "the"), 1);
+        assert_eq!(levenshtein_distance("teh", "the"), 2);
+        assert_eq!(damerau_levenshtein_distance("ca", "abc"), 2);
+        assert_eq!(damerau_levenshtein_distance("abc", "ca"), 2);
+        assert_eq!(damerau_levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(damerau_levenshtein_distance("", "abc"), 3);
+        assert_eq!(damerau_levenshtein_distance("", ""), 0);
+
+        assert_eq!(damerau_levenshtein_similarity("teh", "the"), 1. - 1. / 3.);
+        assert_eq!(damerau_levenshtein_similarity("ca", "abc"), 1. - 2. / 3.);
+        assert_eq!(damerau_levenshtein_similarity("", ""), 1.);
+        // Transposed multi-byte chars count as a single edit as well.
+        assert_eq!(damerau_levenshtein_similarity("Köln", "Kölm"), 0.75);
+        assert_eq!(damerau_levenshtein_similarity("öä", "äö"), 0.5);
+    }
+
+    #[test]
+    fn test_hamming() {
+        assert_eq!(hamming_distance("karolin", "kathrin"), Some(3));
+        assert_eq!(hamming_similarity("karolin", "kathrin"), 1. - 3. / 7.);
+        assert_eq!(hamming_distance("karolin", "karoline"), None);
+        assert_eq!(hamming_similarity("karolin", "karoline"), 0.);
+        assert_eq!(hamming_similarity("", ""), 1.);
+
+        // Multi-byte chars count once, even though their byte lengths differ.
+        assert_eq!(hamming_distance("Köln", "Kiln"), Some(1));
+        assert_eq!(hamming_similarity("Köln", "Kiln"), 0.75);
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        assert_eq!(levenshtein_distance("café", "cafe"), 1);
+        assert_eq!(levenshtein_similarity("café", "cafe"), 0.75);
+        assert_eq!(levenshtein_matrix("café", "cafe").len(), 5);
+        assert_eq!(levenshtein_matrix("café", "cafe")[4].len(), 5);
+
+        assert_eq!(levenshtein_distance("\u{1F44D}", "\u{1F44E}"), 1);
+        assert_eq!(levenshtein_distance("ok\u{1F44D}", "ok"), 1);
+        assert_eq!(levenshtein_similarity("ok\u{1F44D}", "ok!"), 2. / 3.);
+
+        let report = levenshtein_report("Grüße", "Grusse");
+        assert_eq!(report.distance, 3);
+        assert_eq!(report.similarity, 0.5);
+        assert_eq!(
+            report.weighted_similarity,
+            weighted_levenshtein_similarity("Grüße", "Grusse")
+        );
+
+        // Only the edited char counts, no matter how many bytes it takes.
+        assert_eq!(
+            weighted_levenshtein_similarity("café", "cafe"),
+            weighted_levenshtein_similarity("cafx", "cafe")
+        );
+    }
+
+    #[test]
+    fn test_align() {
+        let span = |kind, query: std::ops::Range<usize>, value: std::ops::Range<usize>| Span {
+            kind,
+            query,
+            value,
+        };
+
+        assert_eq!(
+            align("kitten", "sitting"),
+            vec![
+                span(SpanKind::Substitute, 0..1, 0..1),
+                span(SpanKind::Match, 1..4, 1..4),
+                span(SpanKind::Substitute, 4..5, 4..5),
+                span(SpanKind::Match, 5..6, 5..6),
+                span(SpanKind::Insert, 6..6, 6..7),
+            ]
+        );
+        assert_eq!(
+            align("hello world", "world"),
+            vec![
+                span(SpanKind::Delete, 0..6, 0..0),
+                span(SpanKind::Match, 6..11, 0..5),
+            ]
+        );
+
+        // The offsets count chars, not bytes.
+        assert_eq!(
+            align("grüße", "grüsse"),
+            vec![
+                span(SpanKind::Match, 0..3, 0..3),
+                span(SpanKind::Insert, 3..3, 3..4),
+                span(SpanKind::Substitute, 3..4, 4..5),
+                span(SpanKind::Match, 4..5, 5..6),
+            ]
+        );
+        assert_eq!(align("", ""), vec![]);
+        assert_eq!(align("", "ab"), vec![span(SpanKind::Insert, 0..0, 0..2)]);
+    }
+
+    #[test]
+    fn test_trimmed_affixes_match_full_matrix() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut random = |len: usize| -> String {
+            (0..rng.gen_range(0..=len))
+                .map(|_| ['a', 'b', 'ß', 'c'][rng.gen_range(0..4)])
+                .collect()
+        };
+
+        for _ in 0..2000 {
+            let (prefix, suffix) = (random(4), random(4));
+            let a = format!("{prefix}{}{suffix}", random(5));
+            let b = format!("{prefix}{}{suffix}", random(5));
+
+            let matrix = levenshtein_matrix(&a, &b);
+            let full_distance = matrix[a.chars().count()][b.chars().count()];
+            assert_eq!(levenshtein_distance(&a, &b), full_distance);
+
+            // The incremental version still computes the whole matrix.
+            let mut incremental = IncrementalLevenshtein::new("", &b);
+            assert_eq!(
+                weighted_levenshtein_similarity(&a, &b),
+                incremental.weighted_similarity(&a),
+                "{a:?} {b:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::levenshtein::base::normalized_levenshtein_similarity;
+    use simple_search::levenshtein::incremental::IncrementalLevenshtein;
+    use simple_search::search_engine;
+    use simple_search::search_engine::SearchEngine;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Book {
+        title: String,
+        description: String,
+        author: String,
+    }
+
+    fn books() -> Vec<Book> {
+        [
+            (
+                "The Winds of Winter",
+                "The sixth book in the A Song of Ice and Fire series.",
+                "George R. R. Martin",
+            ),
+            (
+                "The Great Gatsby",
+                "A classic novel of the roaring twenties.",
+                "F. Scott Fitzgerald",
+            ),
+            (
+                "Brave New World",
+                "A visionary and disturbing novel about a dystopian future.",
+                "Aldous Huxley",
+            ),
+        ]
+        .into_iter()
+        .map(|(title, description, author)| Book {
+            title: title.to_string(),
+            description: description.to_string(),
+            author: author.to_string(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_macro_matches_builder() {
+        let mut built = SearchEngine::new()
+            .with_values(books())
+            .with_state(
+                |book| IncrementalLevenshtein::new("", &book.title),
+                |s, _, q| s.weighted_similarity(q),
+            )
+            .with_state_and_weight(
+                0.8,
+                |book| IncrementalLevenshtein::new("", &book.author),
+                |s, _, q| s.weighted_similarity(q),
+            )
+            .with_state_and_weight(
+                0.5,
+                |book| IncrementalLevenshtein::new("", &book.description),
+                |s, _, q| s.weighted_similarity(q),
+            );
+
+        let mut expanded = search_engine! {
+            values: books(),
+            title => IncrementalLevenshtein @ 1.0,
+            author => IncrementalLevenshtein @ 0.8,
+            description => IncrementalLevenshtein @ 0.5,
+        };
+
+        for query in ["Fire adn water", "Fitzereld", "brave"] {
+            assert_eq!(built.similarities(query), expanded.similarities(query));
+        }
+    }
+
+    #[test]
+    fn test_macro_stateless() {
+        let engine = search_engine! {
+            values: books(),
+            title => normalized_levenshtein_similarity @ 1.0,
+            author => simple_search::levenshtein::base::normalized_levenshtein_similarity @ 0.5,
+        };
+
+        assert_eq!(
+            engine.search_descending("Brave New Wrld")[0].title,
+            "Brave New World"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::levenshtein::base::normalized_levenshtein_similarity;
+    use simple_search::metrics::acronym::{acronym_similarity, word_initials};
+    use simple_search::metrics::articles::{
+        article_insensitive, article_insensitive_with, strip_leading_articles,
+    };
+    use simple_search::metrics::caseless::{
+        caseless_levenshtein_similarity, fold_case, strip_diacritics, Normalizer,
+    };
+    use simple_search::metrics::confusables::{confusable_insensitive, fold_confusables};
+    use simple_search::metrics::ngram::{
+        bag_similarity, dice_coefficient, ngram_jaccard_similarity, trigram_similarity,
+    };
+    use simple_search::metrics::phonetic::{name_similarity, name_similarity_with, soundex};
+
+    #[test]
+    fn test_word_initials() {
+        assert_eq!(word_initials("getBookName"), "gBN");
+        assert_eq!(word_initials("HTTPServer_v2"), "HSv2");
+        assert_eq!(word_initials("snake_case-name with space"), "scnws");
+        assert_eq!(word_initials(""), "");
+    }
+
+    #[test]
+    fn test_acronym_similarity() {
+        let strong = acronym_similarity("getBookName", "gBN");
+        let weak = acronym_similarity("getBookName", "xyz");
+
+        assert_eq!(strong, 1.);
+        assert_eq!(acronym_similarity("get_book_name", "gbn"), 1.);
+        assert!(weak < 0.5);
+        assert!(strong > weak);
+    }
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Tymczak"), "T522");
+        assert_eq!(soundex("Pfister"), "P236");
+        assert_eq!(soundex("Lee"), "L000");
+        assert_eq!(soundex("123"), "");
+    }
+
+    #[test]
+    fn test_name_similarity() {
+        let text = normalized_levenshtein_similarity("jon", "john");
+        let name = name_similarity("John", "Jon");
+        assert!(name > text);
+        assert!(name < 1.);
+
+        assert_eq!(
+            name_similarity("Maria", "Bob"),
+            normalized_levenshtein_similarity("maria", "bob")
+        );
+        assert_eq!(name_similarity("John", "john"), 1.);
+        assert_eq!(name_similarity_with("John", "Jon", 0.5, 0.2), text);
+    }
+
+    #[test]
+    fn test_confusables() {
+        let spoofed = "p\u{0430}yp\u{0430}l";
+        assert_ne!(spoofed, "paypal");
+        assert_eq!(fold_confusables(spoofed), "paypal");
+
+        assert!(normalized_levenshtein_similarity(spoofed, "paypal") < 0.7);
+        let metric = confusable_insensitive(normalized_levenshtein_similarity);
+        assert_eq!(metric(spoofed, "paypal"), 1.);
+        assert!(metric("p\u{0430}yp\u{0430}", "paypal") > 0.8);
+    }
+
+    #[test]
+    fn test_bag_similarity() {
+        assert_eq!(bag_similarity("listen", "silent"), 1.);
+        assert_eq!(bag_similarity("dormitory", "dirtyroom"), 1.);
+        assert_eq!(bag_similarity("abc", "xyz"), 0.);
+        assert_eq!(bag_similarity("", ""), 1.);
+        assert_eq!(bag_similarity("abc", ""), 0.);
+
+        // Repeated chars are only shared as often as they occur in both strings.
+        assert_eq!(bag_similarity("aab", "ab"), 0.8);
+        assert!(
+            bag_similarity("hlelo", "hello") > normalized_levenshtein_similarity("hlelo", "hello")
+        );
+    }
+
+    #[test]
+    fn test_trigram_similarity() {
+        assert_eq!(trigram_similarity("hello", "hello"), 1.);
+        assert_eq!(trigram_similarity("hello", "hallo"), 0.2);
+        assert_eq!(ngram_jaccard_similarity("hello", "hallo", 2), 2. / 6.);
+        assert!(
+            trigram_similarity("red wine glass", "glass red wine")
+                > normalized_levenshtein_similarity("red wine glass", "glass red wine")
+        );
+
+        // Strings shorter than n are compared as a whole.
+        assert_eq!(trigram_similarity("ab", "ab"), 1.);
+        assert_eq!(trigram_similarity("ab", "abc"), 0.);
+        assert_eq!(trigram_similarity("", ""), 1.);
+        assert_eq!(trigram_similarity("", "abc"), 0.);
+        assert_eq!(ngram_jaccard_similarity("abc", "abc", 0), 1.);
+    }
+
+    #[test]
+    fn test_dice_coefficient() {
+        assert_eq!(dice_coefficient("night", "nacht"), 0.25);
+        assert_eq!(dice_coefficient("night", "night"), 1.);
+        assert_eq!(dice_coefficient("abc", "xyz"), 0.);
+
+        // Repeated bigrams are only shared as often as they occur in both strings.
+        assert_eq!(dice_coefficient("aaa", "aa"), 2. / 3.);
+
+        // Strings with fewer than two chars are compared for equality.
+        assert_eq!(dice_coefficient("a", "a"), 1.);
+        assert_eq!(dice_coefficient("a", "b"), 0.);
+        assert_eq!(dice_coefficient("", ""), 1.);
+        assert_eq!(dice_coefficient("a", "ab"), 0.);
+    }
+
+    #[test]
+    fn test_caseless() {
+        assert_eq!(fold_case("CAFÉ"), "café");
+        assert_eq!(strip_diacritics("Crème Brûlée"), "Creme Brulee");
+        assert_eq!(strip_diacritics("cafe\u{301}"), "cafe");
+        assert_eq!(strip_diacritics("Straße Ωμέγα"), "Straße Ωμέγα");
+
+        assert_eq!(caseless_levenshtein_similarity("Cafe", "café"), 1.);
+        assert_eq!(caseless_levenshtein_similarity("Cafe", "CAFÉ"), 1.);
+        assert!(normalized_levenshtein_similarity("Cafe", "café") < 1.);
+
+        let case_only = Normalizer::new()
+            .fold_case()
+            .wrap(normalized_levenshtein_similarity);
+        assert_eq!(case_only("Cafe", "CAFE"), 1.);
+        assert!(case_only("Cafe", "CAFÉ") < 1.);
+        assert_eq!(Normalizer::new().normalize("Café"), "Café");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-normalization")]
+    fn test_nfc_normalizer() {
+        use simple_search::metrics::caseless::nfc;
+
+        assert_eq!(nfc("cafe\u{301}"), "caf\u{e9}");
+
+        let metric = Normalizer::new()
+            .nfc()
+            .wrap(normalized_levenshtein_similarity);
+        assert_eq!(metric("cafe\u{301}", "caf\u{e9}"), 1.);
+        assert!(normalized_levenshtein_similarity("cafe\u{301}", "caf\u{e9}") < 1.);
+    }
+
+    #[test]
+    fn test_leading_articles() {
+        assert_eq!(strip_leading_articles("The Great Gatsby"), "Great Gatsby");
+        assert_eq!(strip_leading_articles("an  Apple"), "Apple");
+        assert_eq!(
+            strip_leading_articles("Theory of Everything"),
+            "Theory of Everything"
+        );
+        assert_eq!(strip_leading_articles("The"), "The");
+
+        let metric = article_insensitive(normalized_levenshtein_similarity);
+        assert_eq!(metric("Great Gatsby", "The Great Gatsby"), 1.);
+        assert!(metric("Great Gatsby", "The Grate Gatsby") < 1.);
+
+        let german =
+            article_insensitive_with(["der", "die", "das"], normalized_levenshtein_similarity);
+        assert_eq!(german("Die Verwandlung", "Verwandlung"), 1.);
+        assert!(german("The Trial", "Trial") < 1.);
+    }
+}
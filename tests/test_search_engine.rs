@@ -0,0 +1,1596 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::levenshtein::base::{
+        levenshtein_distance, normalized_levenshtein_similarity, weighted_levenshtein_similarity,
+    };
+    use simple_search::levenshtein::incremental::IncrementalLevenshtein;
+    use simple_search::levenshtein::partial::partial_ratio;
+    use simple_search::search_engine::{CombineMode, FieldAccessor, SearchEngine};
+    use simple_search::searchable::Searchable;
+    use simple_search::validate::Warning;
+    use std::borrow::Cow;
+    use std::cell::{Cell, RefCell};
+    use std::collections::{HashMap, HashSet};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const VALUES: [&str; 6] = ["hello", "world", "foo", "bar", "help", "hollow"];
+
+    #[test]
+    fn test_unsorted_similarities() {
+        let mut engine = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+
+        let unsorted = engine.unsorted_similarities("hallo");
+        let unsorted_values: Vec<_> = unsorted.iter().map(|(v, _)| **v).collect();
+        assert_eq!(unsorted_values, VALUES.to_vec());
+
+        let unsorted: HashMap<_, _> = unsorted
+            .into_iter()
+            .map(|(v, s)| (v.to_string(), s))
+            .collect();
+        let sorted: HashMap<_, _> = engine
+            .similarities("hallo")
+            .into_iter()
+            .map(|(v, s)| (v.to_string(), s))
+            .collect();
+        assert_eq!(unsorted, sorted);
+
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+        let unsorted_values: Vec<_> = engine
+            .unsorted_similarities("hallo")
+            .into_iter()
+            .map(|(v, _)| *v)
+            .collect();
+        assert_eq!(unsorted_values, VALUES.to_vec());
+    }
+
+    #[test]
+    fn test_from_iter_values() {
+        let mut from_vec = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+
+        let mut from_iter = SearchEngine::from_iter_values(VALUES.iter().copied()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+
+        for query in ["hallo", "wrld", "fo"] {
+            assert_eq!(from_vec.similarities(query), from_iter.similarities(query));
+        }
+
+        let mut added = SearchEngine::new()
+            .with_state(
+                |v: &&str| IncrementalLevenshtein::new("", v),
+                |s, _, q: &str| s.weighted_similarity(q),
+            )
+            .with_values_iter(VALUES.iter().copied().take(2));
+        added.add_values_iter(VALUES.iter().copied().skip(2));
+        assert_eq!(added.values().copied().collect::<Vec<_>>(), VALUES);
+        assert_eq!(from_vec.similarities("hallo"), added.similarities("hallo"));
+
+        let collected: SearchEngine<&str, str, _, _> = VALUES.iter().copied().collect();
+        let collected = collected.with(|v, q| weighted_levenshtein_similarity(q, v));
+        assert_eq!(collected.search("hallo")[0], &"hello");
+        assert_eq!(collected.len(), VALUES.len());
+    }
+
+    #[test]
+    fn test_search_highlighted() {
+        let mut engine = SearchEngine::new()
+            .with_values(vec!["<b>Größe</b> & mehr", "world"])
+            .with_state(
+                |v| IncrementalLevenshtein::new("", v),
+                |s, _, q| s.weighted_similarity(q),
+            );
+
+        let results = engine.search_highlighted("Größe", "<mark>", "</mark>");
+        let (highlighted, _) = results.iter().find(|(h, _)| h.contains("mehr")).unwrap();
+
+        assert_eq!(
+            highlighted,
+            "&lt;b&gt;<mark>Größe</mark>&lt;/b&gt; &amp; mehr"
+        );
+
+        let engine = SearchEngine::new()
+            .with_values(vec!["a<c"])
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+        let results = engine.search_highlighted("ac", "[", "]");
+        assert_eq!(results[0].0, "[a]&lt;[c]");
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let engine = SearchEngine::with_capacity(100);
+        assert!(engine.capacity() >= 100);
+
+        let engine = engine
+            .with_values(VALUES.to_vec())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+        assert!(engine.capacity() >= 100);
+
+        let reference = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+        assert_eq!(
+            engine.similarities("hallo"),
+            reference.similarities("hallo")
+        );
+    }
+
+    #[test]
+    fn test_query_state() {
+        let preprocessed = Cell::new(0);
+
+        let mut engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with_query_state(
+                |v| IncrementalLevenshtein::new("", v),
+                |q: &str| {
+                    preprocessed.set(preprocessed.get() + 1);
+                    q.to_lowercase()
+                },
+                |s, q, _| s.weighted_similarity(q),
+            );
+
+        engine.search("HALLO");
+        assert_eq!(preprocessed.get(), 1);
+
+        let results = engine.search("WORLD");
+        assert_eq!(preprocessed.get(), 2);
+        assert_eq!(results.first(), Some(&&"world"));
+    }
+
+    #[test]
+    fn test_drain_filter() {
+        let mut engine = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+
+        let removed = engine.drain_filter(|v| v.starts_with('h'));
+        assert_eq!(removed, vec!["hello", "help", "hollow"]);
+
+        let mut remaining = engine.search("hello");
+        remaining.sort();
+        assert_eq!(remaining, vec![&"bar", &"foo", &"world"]);
+    }
+
+    #[test]
+    fn test_search_session() {
+        let new_engine = || {
+            SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+                |v| IncrementalLevenshtein::new("", v),
+                |s, _, q| s.weighted_similarity(q),
+            )
+        };
+
+        let mut engine = new_engine();
+        let mut session = engine.session();
+        for c in "helx".chars() {
+            session.type_char(c);
+            session.results();
+        }
+        session.backspace();
+        session.type_char('l');
+        assert_eq!(session.query(), "hell");
+        let results: Vec<_> = session
+            .results()
+            .into_iter()
+            .map(|(v, s)| (*v, s))
+            .collect();
+
+        let mut reference = new_engine();
+        let expected: Vec<_> = reference
+            .similarities("hell")
+            .into_iter()
+            .map(|(v, s)| (*v, s))
+            .collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_values() {
+        let mut engine = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+        engine.search("hallo");
+        engine.add_value("added");
+
+        let values: Vec<_> = engine.values().copied().collect();
+        assert_eq!(engine.values().len(), VALUES.len() + 1);
+        assert_eq!(values[..VALUES.len()], VALUES);
+        assert_eq!(values.last(), Some(&"added"));
+    }
+
+    #[test]
+    fn test_ordering_options() {
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+
+        let mut ascending = engine.similarities_ascending("hallo");
+        let descending = engine.similarities_descending("hallo");
+        assert_eq!(engine.similarities("hallo"), descending);
+        assert_eq!(
+            engine.search_ascending("hallo"),
+            ascending.iter().map(|v| v.0).collect::<Vec<_>>()
+        );
+        ascending.reverse();
+        let scores = |values: &[(&&str, f64)]| values.iter().map(|v| v.1).collect::<Vec<_>>();
+        assert_eq!(scores(&ascending), scores(&descending));
+
+        let top_k = engine.similarities_top_k("hallo", 2);
+        assert_eq!(scores(&top_k), scores(&descending[..2]));
+        assert!(engine.similarities_top_k("hallo", 0).is_empty());
+        assert_eq!(engine.search_top_k("hallo", 100).len(), VALUES.len());
+
+        let above = engine.similarities_above("hallo", 0.5);
+        assert!(above.iter().all(|(_, s)| *s >= 0.5));
+        assert_eq!(
+            above.len(),
+            descending.iter().filter(|(_, s)| *s >= 0.5).count()
+        );
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let states = Cell::new(0);
+        let updates = Cell::new(0);
+
+        let mut engine = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| {
+                states.set(states.get() + 1);
+                IncrementalLevenshtein::new("", v)
+            },
+            |s, _, q| {
+                updates.set(updates.get() + 1);
+                s.weighted_similarity(q)
+            },
+        );
+        assert_eq!(states.get(), VALUES.len());
+
+        let before = engine.similarities("hallo");
+        let before: Vec<_> = before.into_iter().map(|(v, s)| (*v, s)).collect();
+        assert_eq!(updates.get(), VALUES.len());
+
+        engine.invalidate();
+        assert_eq!(states.get(), 2 * VALUES.len());
+        assert!(engine
+            .get_values_with_state()
+            .iter()
+            .all(|(state, _)| state.0.matrix().len() == 1));
+
+        let after: Vec<_> = engine
+            .similarities("hallo")
+            .into_iter()
+            .map(|(v, s)| (*v, s))
+            .collect();
+        assert_eq!(updates.get(), 2 * VALUES.len());
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_invalidate_recomputes_cached_query() {
+        let computations = Cell::new(0);
+        // Every state caches the similarity to the last query, serving a repeated query without recomputing it.
+        let mut engine = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |_| None::<(String, f64)>,
+            |cache, v, q| match cache {
+                Some((last, similarity)) if last == q => *similarity,
+                _ => {
+                    computations.set(computations.get() + 1);
+                    let similarity = normalized_levenshtein_similarity(v, q);
+                    *cache = Some((q.to_string(), similarity));
+                    similarity
+                }
+            },
+        );
+
+        let first = engine.similarities("hallo");
+        let first: Vec<_> = first.into_iter().map(|(v, s)| (*v, s)).collect();
+        assert_eq!(computations.get(), VALUES.len());
+        engine.similarities("hallo");
+        assert_eq!(computations.get(), VALUES.len());
+
+        engine.invalidate();
+        let repeated: Vec<_> = engine
+            .similarities("hallo")
+            .into_iter()
+            .map(|(v, s)| (*v, s))
+            .collect();
+        assert_eq!(computations.get(), 2 * VALUES.len());
+        assert_eq!(first, repeated);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Book {
+        title: String,
+        author: String,
+    }
+
+    #[test]
+    fn test_field_weights() {
+        let books = || {
+            vec![
+                Book {
+                    title: "The Great Gatsby".to_string(),
+                    author: "F. Scott Fitzgerald".to_string(),
+                },
+                Book {
+                    title: "Brave New World".to_string(),
+                    author: "Aldous Huxley".to_string(),
+                },
+            ]
+        };
+
+        let mut weights = HashMap::new();
+        weights.insert("title".to_string(), 1.0);
+        weights.insert("author".to_string(), 0.5);
+
+        let fields: Vec<(&str, FieldAccessor<Book>)> = vec![
+            ("title", Arc::new(|b| b.title.as_str())),
+            ("author", Arc::new(|b| b.author.as_str())),
+            ("isbn", Arc::new(|_| "0")),
+        ];
+
+        let engine = SearchEngine::new().with_values(books()).with_field_weights(
+            &weights,
+            fields,
+            |f, q: &str| normalized_levenshtein_similarity(f, q),
+        );
+
+        let results = engine.similarities("Aldous Huxley");
+        assert_eq!(results[0].0.author, "Aldous Huxley");
+        assert_eq!(results[0].1, 0.5);
+
+        let results = engine.similarities("Brave New World");
+        assert_eq!(results[0].0.title, "Brave New World");
+        assert_eq!(results[0].1, 1.0);
+
+        let reference = SearchEngine::new()
+            .with_values(books())
+            .with(|b: &Book, q: &str| normalized_levenshtein_similarity(&b.title, q))
+            .with_weight(0.5, |b: &Book, q: &str| {
+                normalized_levenshtein_similarity(&b.author, q)
+            });
+        assert_eq!(
+            engine.similarities("Fitzgerald"),
+            reference.similarities("Fitzgerald")
+        );
+
+        // Every field with a weight is its own labeled layer, the isbn without a weight has none.
+        let explanation = engine.explain("Aldous Huxley");
+        let labels: Vec<_> = explanation[1]
+            .2
+            .iter()
+            .map(|(label, _, _)| *label)
+            .collect();
+        assert_eq!(labels, vec![Some("title"), Some("author")]);
+        assert_eq!(explanation[1].2[1], (Some("author"), 1., 0.5));
+
+        let mut engine = engine;
+        engine.set_weight(1, 1.);
+        assert_eq!(engine.similarities("Aldous Huxley")[0].1, 1.);
+
+        // Accessors can capture state.
+        let prefix = String::from("Brave");
+        let engine = SearchEngine::new()
+            .with_values(books())
+            .with_fields_weighted(
+                vec![(
+                    "starts_with_prefix",
+                    1.,
+                    Arc::new(move |b: &Book| {
+                        if b.title.starts_with(&prefix) {
+                            b.title.as_str()
+                        } else {
+                            ""
+                        }
+                    }),
+                )],
+                |f, q: &str| normalized_levenshtein_similarity(f, q),
+            );
+        let results = engine.similarities("Brave New World");
+        assert_eq!(results[0], (&books()[1], 1.));
+        assert!(results[1].1 < 1.);
+    }
+
+    #[test]
+    fn test_shards() {
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+        let sort = |results: &mut Vec<(&str, f64)>| {
+            results.sort_by(|(a, x), (b, y)| x.total_cmp(y).then(a.cmp(b)));
+        };
+
+        let mut expected: Vec<_> = engine
+            .similarities("hallo")
+            .into_iter()
+            .map(|(v, s)| (*v, s))
+            .collect();
+        sort(&mut expected);
+
+        let shards = engine.shards(4);
+        assert_eq!(
+            shards.iter().map(|s| s.values().len()).collect::<Vec<_>>(),
+            vec![2, 2, 1, 1]
+        );
+
+        let mut merged: Vec<_> = shards
+            .iter()
+            .flat_map(|shard| shard.similarities("hallo"))
+            .map(|(v, s)| (*v, s))
+            .collect();
+        sort(&mut merged);
+
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn test_similarities_by() {
+        let engine = SearchEngine::new()
+            .with_values(vec!["ab", "abc", "abcd", "xyz", "x"])
+            .with(|v: &&str, q: &str| if v.starts_with(q) { 1. } else { 0. });
+
+        let results = engine.similarities_by("ab", |(a, x), (b, y)| {
+            y.total_cmp(x).then(a.len().cmp(&b.len()))
+        });
+
+        assert_eq!(
+            results,
+            vec![
+                (&"ab", 1.),
+                (&"abc", 1.),
+                (&"abcd", 1.),
+                (&"x", 0.),
+                (&"xyz", 0.)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_consuming_order() {
+        let engine = SearchEngine::new()
+            .with_values(vec!["b", "a", "nan", "c", "d", "e"])
+            .with(|v: &&str, _: &str| match *v {
+                "nan" => f64::NAN,
+                "a" | "b" | "c" => 0.5,
+                _ => 0.25,
+            });
+
+        let borrowed: Vec<_> = engine.search("").into_iter().copied().collect();
+        assert_eq!(borrowed, vec!["b", "a", "c", "d", "e", "nan"]);
+        assert_eq!(engine.clone().into_search(""), borrowed);
+        #[cfg(feature = "rayon")]
+        assert_eq!(engine.clone().into_par_search(""), borrowed);
+    }
+
+    #[test]
+    fn test_set_weight() {
+        let mut engine = SearchEngine::new()
+            .with_values(vec!["short", "a much longer value"])
+            .with(|v: &&str, _: &str| if v.len() < 10 { 1. } else { 0. })
+            .with_weight(0.5, |v: &&str, _: &str| if v.len() < 10 { 0. } else { 1. });
+
+        assert_eq!(
+            engine.search_descending(""),
+            vec![&"short", &"a much longer value"]
+        );
+
+        engine.set_weight(0, 0.25);
+        assert_eq!(
+            engine.search_descending(""),
+            vec![&"a much longer value", &"short"]
+        );
+
+        engine.set_weight(1, 0.1);
+        assert_eq!(
+            engine.similarities_descending(""),
+            vec![(&"short", 0.25), (&"a much longer value", 0.1)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_weight_out_of_bounds() {
+        let mut engine = SearchEngine::<&str, str, _, _>::new().with(|_, _| 1.);
+        engine.set_weight(1, 0.5);
+    }
+
+    #[test]
+    fn test_count_above() {
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+        let mut stateful = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+
+        for threshold in [0., 0.2, 0.5, 1.] {
+            let expected = engine.search_above("hallo", threshold).len();
+            assert_eq!(engine.count_above("hallo", threshold), expected);
+            assert_eq!(stateful.count_above("hallo", threshold), expected);
+            #[cfg(feature = "rayon")]
+            assert_eq!(engine.par_count_above("hallo", threshold), expected);
+        }
+    }
+
+    #[test]
+    fn test_with_limit() {
+        let unlimited = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| normalized_levenshtein_similarity(q, v));
+        let limited = unlimited.clone().with_limit(2);
+
+        for query in ["hallo", "word", "fo", ""] {
+            let best: Vec<_> = unlimited
+                .similarities_descending(query)
+                .into_iter()
+                .take(2)
+                .collect();
+
+            assert_eq!(limited.similarities_descending(query), best);
+            assert_eq!(limited.similarities(query), best);
+
+            let mut ascending = best.clone();
+            ascending.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+            assert_eq!(limited.similarities_ascending(query), ascending);
+            assert!(limited.search(query).len() <= 2);
+            assert_eq!(limited.similarities_top_k(query, 3).len(), 3);
+        }
+
+        // Limited and unlimited searches order equal scores alike, including -0.0 and 0.0.
+        let zeros = SearchEngine::new()
+            .with_values(vec!["a", "b", "c"])
+            .with_weight_using(1., CombineMode::Sum, |v: &&str, _: &str| match *v {
+                "b" => 0.,
+                _ => -0.,
+            });
+        let unlimited: Vec<_> = zeros.search("q").into_iter().copied().collect();
+        assert_eq!(unlimited, vec!["a", "b", "c"]);
+        let limited: Vec<_> = zeros
+            .with_limit(3)
+            .search("q")
+            .into_iter()
+            .copied()
+            .collect();
+        assert_eq!(limited, unlimited);
+    }
+
+    #[test]
+    fn test_from_sorted_unique() {
+        let engine = SearchEngine::<&str, str, _, _>::from_sorted_unique(vec![
+            "apple", "banana", "cherry", "date",
+        ])
+        .with(|v, q| normalized_levenshtein_similarity(q, v));
+
+        for value in ["apple", "banana", "cherry", "date"] {
+            assert_eq!(engine.lookup_exact(value), Some(&value));
+        }
+        assert_eq!(engine.lookup_exact("blueberry"), None);
+        assert_eq!(engine.search_descending("banan")[0], &"banana");
+
+        let mut engine = engine;
+        engine.add_value("avocado");
+        assert_eq!(engine.lookup_exact("avocado"), Some(&"avocado"));
+        assert_eq!(engine.lookup_exact("date"), Some(&"date"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_from_sorted_unique_unsorted() {
+        SearchEngine::<&str, str, _, _>::from_sorted_unique(vec!["banana", "apple"]);
+    }
+
+    #[test]
+    fn test_idf_boost() {
+        let tokenizer = |s: &str| s.split_whitespace().map(str::to_lowercase).collect();
+        let engine = SearchEngine::new()
+            .with_values(vec![
+                "the quick fox",
+                "the lazy dog",
+                "the red fox",
+                "the zebra",
+                "a quokka",
+            ])
+            .with_idf_boost(tokenizer);
+
+        // "zebra" occurs once, "the" and "fox" are common.
+        let results = engine.similarities_descending("the zebra fox");
+        assert_eq!(results[0].0, &"the zebra");
+        assert!(results[0].1 > results[1].1);
+        assert_eq!(results[1].0, &"the quick fox");
+        assert_eq!(results.last().unwrap(), &(&"a quokka", 0.));
+
+        // The document frequencies follow the values, as they are added or removed.
+        let mut changed = engine.clone();
+        changed.retain(|v| !v.contains("fox"));
+        changed.add_value("the zebra crossing");
+        let rebuilt = SearchEngine::new()
+            .with_values(vec![
+                "the lazy dog",
+                "the zebra",
+                "a quokka",
+                "the zebra crossing",
+            ])
+            .with_idf_boost(tokenizer);
+        assert_eq!(
+            changed.similarities("the zebra fox"),
+            rebuilt.similarities("the zebra fox")
+        );
+    }
+
+    #[test]
+    fn test_update_value() {
+        let mut engine = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+        engine.search("hallo");
+
+        assert_eq!(engine.update_value(2, "hallo"), Some("foo"));
+        assert_eq!(engine.update_value(VALUES.len(), "bar"), None);
+
+        assert_eq!(engine.get(2), Some(&"hallo"));
+        assert_eq!(engine.values().len(), VALUES.len());
+        assert_eq!(engine.search_descending("hallo")[0], &"hallo");
+        assert!(!engine.search("foo").contains(&&"foo"));
+    }
+
+    #[test]
+    fn test_with_distance() {
+        let distance = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with_distance(|v, q| levenshtein_distance(q, v) as f64);
+        let similarity = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| 1. / (1. + levenshtein_distance(q, v) as f64));
+
+        for query in ["hallo", "wrld", "help"] {
+            assert_eq!(distance.similarities(query), similarity.similarities(query));
+
+            let closest = distance.search_descending(query)[0];
+            assert_eq!(
+                levenshtein_distance(query, closest),
+                VALUES
+                    .iter()
+                    .map(|v| levenshtein_distance(query, v))
+                    .min()
+                    .unwrap()
+            );
+        }
+        assert_eq!(distance.similarities_descending("hello")[0], (&"hello", 1.));
+    }
+
+    #[test]
+    fn test_search_dedup_by() {
+        let editions = vec![
+            ("Dune", "Dune (1965)"),
+            ("Dune", "Dune: Deluxe Edition"),
+            ("Emma", "Emma"),
+            ("Dune", "Dune"),
+            ("Emma", "Emma, Annotated"),
+        ];
+        let engine = SearchEngine::new()
+            .with_values(editions)
+            .with(|v: &(&str, &str), q: &str| normalized_levenshtein_similarity(q, v.1));
+
+        let results = engine.search_dedup_by("Dune", |v| v.0);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], (&("Dune", "Dune"), 1.));
+        assert_eq!(results[1].0, &("Emma", "Emma, Annotated"));
+    }
+
+    #[test]
+    fn test_searchable() {
+        struct Novel {
+            title: String,
+            year: u16,
+        }
+
+        impl Searchable for Novel {
+            fn search_text(&self) -> Cow<'_, str> {
+                Cow::Borrowed(&self.title)
+            }
+        }
+
+        let engine = SearchEngine::new()
+            .with_values(vec![
+                Novel {
+                    title: "Emma".to_string(),
+                    year: 1815,
+                },
+                Novel {
+                    title: "Persuasion".to_string(),
+                    year: 1817,
+                },
+            ])
+            .with_searchable();
+        assert_eq!(engine.search_descending("Persuasoin")[0].year, 1817);
+
+        let strings = SearchEngine::new()
+            .with_values(VALUES.iter().map(|v| v.to_string()).collect())
+            .with_searchable();
+        let strs = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with_searchable();
+        assert_eq!(
+            strings.similarities("hallo")[0],
+            (&"hello".to_string(), strs.similarities("hallo")[0].1)
+        );
+    }
+
+    #[test]
+    fn test_match_percentages() {
+        let scores = [1., 0.5, 0.004, 0.005, 0.994, 0.995, 1.5, -0.2, f64::NAN];
+        let engine = SearchEngine::new()
+            .with_values((0..scores.len()).collect())
+            .with(move |v: &usize, _: &str| scores[*v]);
+
+        let mut percentages = engine.match_percentages("");
+        percentages.sort();
+        assert_eq!(
+            percentages,
+            vec![
+                (&0, 100),
+                (&1, 50),
+                (&2, 0),
+                (&3, 1),
+                (&4, 99),
+                (&5, 100),
+                (&6, 100),
+                (&7, 0),
+                (&8, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| normalized_levenshtein_similarity(q, v))
+            .with_weight(
+                0.1,
+                |v: &&str, q: &str| {
+                    if v.starts_with(q) {
+                        1.
+                    } else {
+                        0.
+                    }
+                },
+            )
+            .with(|_, _| 0.5);
+
+        let warnings = engine.validate(&["hallo", "wor", "f"]);
+        assert_eq!(
+            warnings,
+            vec![Warning::Dominated {
+                layer: 1,
+                dominated_by: 2,
+                max: 0.1,
+                min: 0.5
+            }]
+        );
+
+        let mut valid = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+        assert!(valid.validate(&["hallo", "wor"]).is_empty());
+
+        // Without observations there are no bounds to compare.
+        assert!(engine.validate(&[]).is_empty());
+        let empty = SearchEngine::<&str, str, _, _>::new()
+            .with(|v, q| normalized_levenshtein_similarity(q, v))
+            .with(|_, _| 0.5);
+        assert!(empty.validate(&["hallo"]).is_empty());
+
+        let constant = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|_: &&str, _: &str| 0.5)
+            .with(|_, _| 0.5);
+        assert!(constant.validate(&["hallo"]).is_empty());
+
+        // A lower layer still adds to the sum, so it isn't dominated.
+        let sum = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|_: &&str, _: &str| 0.5)
+            .with_weight_using(0.1, CombineMode::Sum, |v: &&str, q: &str| {
+                normalized_levenshtein_similarity(q, v)
+            });
+        assert!(sum.validate(&["hallo", "wor", "f"]).is_empty());
+    }
+
+    #[test]
+    fn test_grouped_search() {
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| normalized_levenshtein_similarity(q, v));
+
+        let groups = engine.grouped_search("hello", &[0.5, 1.]);
+        assert_eq!(
+            groups,
+            vec![(1., vec![&"hello"]), (0.5, vec![&"hollow", &"help"])]
+        );
+
+        let groups = engine.grouped_search("xyz", &[1., 0.5]);
+        assert_eq!(groups, vec![(1., vec![]), (0.5, vec![])]);
+    }
+
+    #[test]
+    fn test_labeled_explain() {
+        let books = vec![("The Great Gatsby", "Fitzgerald"), ("Ulysses", "Joyce")];
+
+        let engine = SearchEngine::new()
+            .with_values(books.clone())
+            .with_labeled_weight("title", 1., |b, q: &str| {
+                normalized_levenshtein_similarity(q, b.0)
+            })
+            .with_weight(0.5, |b, q: &str| normalized_levenshtein_similarity(q, b.1));
+
+        let explanation = engine.explain("Joyce");
+        assert_eq!(explanation.len(), 2);
+
+        let (value, total, layers) = &explanation[1];
+        assert_eq!(*value, &books[1]);
+        assert_eq!(*total, 0.5);
+        assert_eq!(
+            layers,
+            &vec![
+                (
+                    Some("title"),
+                    normalized_levenshtein_similarity("Joyce", "Ulysses"),
+                    normalized_levenshtein_similarity("Joyce", "Ulysses")
+                ),
+                (None, 1., 0.5)
+            ]
+        );
+
+        let mut stateful = SearchEngine::new()
+            .with_values(books.clone())
+            .with_labeled_state(
+                "author",
+                0.8,
+                |b| IncrementalLevenshtein::new("", b.1),
+                |s, _, q| s.similarity(q),
+            );
+        let explanation = stateful.explain("Joyce");
+        assert_eq!(explanation[1].2, vec![(Some("author"), 1., 0.8)]);
+        assert_eq!(explanation[1].1, 0.8);
+    }
+
+    #[test]
+    fn test_empty_query() {
+        let calls = AtomicUsize::new(0);
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q: &str| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                weighted_levenshtein_similarity(q, v)
+            })
+            .with_empty_query_score(1.);
+
+        let results = engine.similarities("");
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+        assert_eq!(results, VALUES.iter().map(|v| (v, 1.)).collect::<Vec<_>>());
+
+        // Every kind of search agrees on the insertion order.
+        let expected: Vec<_> = VALUES.to_vec();
+        assert_eq!(engine.clone().into_search(""), expected);
+        #[cfg(feature = "rayon")]
+        assert_eq!(engine.clone().into_par_search(""), expected);
+        assert_eq!(engine.best_match(""), Some((&"hello", 1.)));
+        assert_eq!(engine.similarities_above("", 1.).len(), VALUES.len());
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+        engine.similarities("h");
+        assert_eq!(calls.load(Ordering::Relaxed), VALUES.len());
+
+        // Without opting in, an empty query is scored like any other query.
+        let scored = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q: &str| weighted_levenshtein_similarity(q, v));
+        let borrowed: Vec<_> = scored.search("").into_iter().copied().collect();
+        assert_eq!(borrowed, scored.clone().into_search(""));
+
+        let mut stateful = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with_state(
+                |v| IncrementalLevenshtein::new("", v),
+                |s, _, q| s.weighted_similarity(q),
+            )
+            .with_empty_query_score(0.)
+            .with_limit(2);
+        assert_eq!(stateful.search(""), vec![&"hello", &"world"]);
+        assert_eq!(stateful.clone().into_search(""), vec!["hello", "world"]);
+    }
+
+    #[test]
+    fn test_search_filtered() {
+        let scored = Cell::new(0);
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q: &str| {
+                assert!(v.starts_with('h'));
+                scored.set(scored.get() + 1);
+                weighted_levenshtein_similarity(q, v)
+            });
+
+        let filtered = engine.search_filtered("hallo", |v| v.starts_with('h'));
+        assert_eq!(scored.get(), 3);
+
+        let prefiltered = SearchEngine::new()
+            .with_values(
+                VALUES
+                    .iter()
+                    .copied()
+                    .filter(|v| v.starts_with('h'))
+                    .collect(),
+            )
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+        assert_eq!(filtered, prefiltered.similarities("hallo"));
+
+        let mut stateful = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with_state(
+                |v| IncrementalLevenshtein::new("", v),
+                |s, _, q| s.weighted_similarity(q),
+            )
+            .with_limit(1);
+        assert_eq!(
+            stateful.search_filtered("hallo", |v| *v != "hello"),
+            vec![(
+                &"hollow",
+                weighted_levenshtein_similarity("hallo", "hollow")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_best_match() {
+        let empty = SearchEngine::<&str, str, _, _>::new()
+            .with(|v: &&str, q: &str| weighted_levenshtein_similarity(q, v));
+        assert_eq!(empty.best_match("hello"), None);
+
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| normalized_levenshtein_similarity(q, v));
+        assert_eq!(engine.best_match("hallo"), Some((&"hello", 0.8)));
+        assert_eq!(engine.best_match_above("hallo", 0.8), Some((&"hello", 0.8)));
+        assert_eq!(engine.best_match_above("hallo", 0.9), None);
+
+        let mut stateful = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.similarity(q),
+        );
+        assert_eq!(stateful.best_match("wrld").map(|(v, _)| *v), Some("world"));
+        assert_eq!(stateful.best_match_above("xyz", 0.5), None);
+        assert_eq!(
+            stateful.into_best_match("wrld").map(|(v, _)| v),
+            Some("world")
+        );
+        assert_eq!(
+            engine.clone().into_best_match("hallo"),
+            Some(("hello", 0.8))
+        );
+
+        // Of equally similar values, the one added first wins.
+        let tied = SearchEngine::new()
+            .with_values(vec!["foo", "bar", "baz"])
+            .with(|_: &&str, _: &str| 0.5);
+        assert_eq!(tied.best_match("query"), Some((&"foo", 0.5)));
+    }
+
+    #[test]
+    fn test_add_value_seeded() {
+        let build = |values: Vec<&'static str>| {
+            SearchEngine::new().with_values(values).with_state(
+                |v| IncrementalLevenshtein::new("", v),
+                |s, _, q| s.weighted_similarity(q),
+            )
+        };
+
+        let mut engine = build(VALUES.to_vec());
+        engine.similarities("hel");
+        engine.add_value_seeded("helmet", "hel");
+
+        let (state, value) = engine.get_values_with_state().last().unwrap();
+        assert_eq!(*value, "helmet");
+        assert_eq!(state.0.matrix().len(), "hel".len() + 1);
+
+        let mut all = VALUES.to_vec();
+        all.push("helmet");
+        let mut fresh = build(all);
+        assert_eq!(engine.similarities("hell"), fresh.similarities("hell"));
+    }
+
+    #[test]
+    fn test_shuffled_ties() {
+        let engine = SearchEngine::new()
+            .with_values((0..12).collect())
+            .with(|v: &i32, _: &str| if *v < 6 { 0.5 } else { 1. });
+
+        let order = |seed| {
+            engine
+                .similarities_shuffled_ties("q", seed)
+                .into_iter()
+                .map(|(v, s)| (*v, s))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(7), order(7));
+        assert!((0..10).any(|seed| order(seed) != order(7)));
+
+        for seed in 0..10 {
+            let results = order(seed);
+            let (high, low) = results.split_at(6);
+            assert!(low.iter().all(|(v, s)| *v < 6 && *s == 0.5));
+            assert!(high.iter().all(|(v, s)| *v >= 6 && *s == 1.));
+        }
+
+        // The ties straddling the limit are shuffled before the results are truncated.
+        let limited = engine.clone().with_limit(8);
+        let mut kept = HashSet::new();
+        for seed in 0..20 {
+            let results = limited.similarities_shuffled_ties("q", seed);
+            assert_eq!(results.len(), 8);
+            assert!(results[..6].iter().all(|(v, s)| **v >= 6 && *s == 1.));
+            assert!(results[6..].iter().all(|(v, s)| **v < 6 && *s == 0.5));
+            kept.extend(results[6..].iter().map(|(v, _)| **v));
+        }
+        assert!(kept.len() > 2);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_into_par_top_k() {
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+
+        for k in [0, 2, 4, 10] {
+            let expected: Vec<_> = engine
+                .clone()
+                .into_par_similarities("hallo")
+                .into_iter()
+                .take(k)
+                .collect();
+            assert_eq!(engine.clone().into_par_top_k("hallo", k), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_par_similarities_with() {
+        // A similarity capturing a cache, that isn't Sync.
+        let cache = RefCell::new(HashMap::new());
+        let engine =
+            SearchEngine::new()
+                .with_values(VALUES.to_vec())
+                .with(move |v: &&str, q: &str| {
+                    *cache
+                        .borrow_mut()
+                        .entry((v.to_string(), q.to_string()))
+                        .or_insert_with(|| weighted_levenshtein_similarity(q, v))
+                });
+
+        let lengths: HashMap<&str, usize> = VALUES.iter().map(|v| (*v, v.len())).collect();
+        let results = engine.par_similarities_with("hallo", &|v: &&str, q: &str| {
+            let penalty = lengths[v].abs_diff(q.len()) as f64 * 0.1;
+            weighted_levenshtein_similarity(q, v) - penalty
+        });
+        assert_eq!(results.len(), VALUES.len());
+        assert_eq!(results[0].0, &"hello");
+        assert!(results.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        // The configured similarity is combined with the maximum, so it never drops below 0.0.
+        assert_eq!(
+            engine.par_similarities_with("hallo", &|v: &&str, q: &str| {
+                weighted_levenshtein_similarity(q, v).max(0.)
+            }),
+            engine.similarities("hallo")
+        );
+    }
+
+    #[test]
+    fn test_exact_flag() {
+        let books = vec![
+            ("The Great Gatsby", "Fitzgerald"),
+            ("Great Expectations", "Dickens"),
+        ];
+
+        let engine = SearchEngine::new()
+            .with_values(books.clone())
+            .with(|b, q: &str| normalized_levenshtein_similarity(q, b.0))
+            .with_weight(0.5, |b, q: &str| normalized_levenshtein_similarity(q, b.1));
+
+        let results = engine.similarities_with_exact_flag("Dickens");
+        assert_eq!(results[0], (&books[1], 0.5, true));
+        assert!(!results[1].2);
+
+        let results = engine.similarities_with_exact_flag("The Great Gatsbee");
+        assert_eq!(results[0].0, &books[0]);
+        assert!(results.iter().all(|(_, _, exact)| !exact));
+
+        let mut stateful = SearchEngine::new().with_values(books.clone()).with_state(
+            |b| IncrementalLevenshtein::new("", b.0),
+            |s, _, q| s.similarity(q),
+        );
+        let results = stateful.similarities_with_exact_flag("The Great Gatsby");
+        assert_eq!(results[0], (&books[0], 1., true));
+    }
+
+    #[test]
+    fn test_scores() {
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+
+        let scores = engine.scores("hallo");
+        let unsorted = engine.unsorted_similarities("hallo");
+        assert_eq!(scores.len(), VALUES.len());
+        for (i, (value, similarity)) in unsorted.into_iter().enumerate() {
+            assert_eq!(*value, VALUES[i]);
+            assert_eq!(scores[i], similarity);
+        }
+
+        let mut stateful = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+        assert_eq!(stateful.scores("hallo"), scores);
+
+        #[cfg(feature = "rayon")]
+        {
+            assert_eq!(engine.par_scores("hallo"), scores);
+            assert_eq!(stateful.par_scores("hallo"), scores);
+        }
+    }
+
+    #[test]
+    fn test_approx_search() {
+        let scored = RefCell::new(Vec::new());
+        let engine =
+            SearchEngine::new()
+                .with_values((0..1000).collect())
+                .with(|v: &i32, _: &str| {
+                    scored.borrow_mut().push(*v);
+                    *v as f64 / 1000.
+                });
+
+        let results = engine.approx_search("q", 50, 5, 42);
+        let sampled = scored.take();
+        assert_eq!(sampled.len(), 50);
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|(v, _)| sampled.contains(v)));
+        assert!(results.windows(2).all(|pair| pair[0].1 > pair[1].1));
+
+        assert_eq!(engine.approx_search("q", 50, 5, 42), results);
+        assert!((0..10).any(|seed| engine.approx_search("q", 50, 5, seed) != results));
+
+        // A sample covering all values is exact.
+        assert_eq!(
+            engine.approx_search("q", 2000, 3, 0),
+            engine.similarities_top_k("q", 3)
+        );
+
+        let mut stateful = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+        let approx = stateful.approx_search("hallo", 3, 3, 7);
+        assert_eq!(approx.len(), 3);
+        for (value, similarity) in approx {
+            assert_eq!(similarity, weighted_levenshtein_similarity("hallo", value));
+        }
+    }
+
+    #[test]
+    fn test_starts_with_ends_with() {
+        let engine = SearchEngine::new().with_values(VALUES.to_vec());
+        assert_eq!(engine.starts_with("hel"), vec![&"hello", &"help"]);
+        assert_eq!(engine.starts_with("Hel"), Vec::<&&str>::new());
+        assert_eq!(engine.starts_with("").len(), VALUES.len());
+
+        let engine = engine.with(|v, q| weighted_levenshtein_similarity(q, v));
+        assert_eq!(engine.ends_with("llo"), vec![&"hello"]);
+        assert_eq!(engine.ends_with("o"), vec![&"hello", &"foo"]);
+        assert_eq!(engine.ends_with("xyz"), Vec::<&&str>::new());
+    }
+
+    #[test]
+    fn test_field_normalization() {
+        struct Article {
+            title: &'static str,
+            description: &'static str,
+        }
+
+        let articles = || {
+            vec![
+                Article {
+                    title: "Dubliners",
+                    description: "a collection of fifteen stories about irish middle class life",
+                },
+                Article {
+                    title: "Ulysses",
+                    description: "a modernist novel following one day in dublin in june",
+                },
+                Article {
+                    title: "Emma",
+                    description: "a comedy of manners about a young woman and matchmaking",
+                },
+            ]
+        };
+        let fields = || -> Vec<(&str, FieldAccessor<Article>)> {
+            vec![
+                ("title", Arc::new(|a| a.title)),
+                ("description", Arc::new(|a| a.description)),
+            ]
+        };
+        let metric = |f: &str, q: &str| normalized_levenshtein_similarity(f, q);
+
+        let mut weights = HashMap::new();
+        weights.insert("title".to_string(), 1.0);
+        weights.insert("description".to_string(), 1.0);
+
+        let raw = SearchEngine::new()
+            .with_values(articles())
+            .with_field_weights(&weights, fields(), metric);
+        let results = raw.similarities("dublin");
+        assert_eq!(results[0].0.title, "Dubliners");
+        // Raw, the long description barely registers next to the short title.
+        assert_eq!(results[1].0.title, "Ulysses");
+        assert!(results[1].1 < 0.2);
+
+        let weighted = |title: f64, description: f64| -> Vec<(&str, f64, FieldAccessor<Article>)> {
+            fields()
+                .into_iter()
+                .zip([title, description])
+                .map(|((name, accessor), weight)| (name, weight, accessor))
+                .collect()
+        };
+        let queries = ["dublin", "emma", "ulysses", "novel"];
+        let normalized = SearchEngine::new()
+            .with_values(articles())
+            .with_field_normalization(weighted(1., 1.), CombineMode::Max, metric, &queries);
+        let results = normalized.similarities("dublin");
+        // Normalized, the best description match scores as high as the best title match would.
+        assert_eq!(results[0].0.title, "Ulysses");
+        assert_eq!(results[0].1, 1.0);
+        assert_eq!(results[1].0.title, "Dubliners");
+        assert!(results[1].1 > 0.5);
+
+        // Every field forms its own labeled layer.
+        let explanation = normalized.explain("dublin");
+        let labels: Vec<_> = explanation[1]
+            .2
+            .iter()
+            .map(|(label, _, _)| *label)
+            .collect();
+        assert_eq!(labels, [Some("title"), Some("description")]);
+        assert_eq!(explanation[1].2[1].1, 1.0);
+
+        // The weights and the combine mode apply to the normalized fields.
+        let summed = SearchEngine::new()
+            .with_values(articles())
+            .with_field_normalization(weighted(1., 0.5), CombineMode::Sum, metric, &queries);
+        for (_, similarity, layers) in summed.explain("dublin") {
+            let (title, description) = (layers[0].1, layers[1].1);
+            assert!((similarity - (title + 0.5 * description)).abs() < 1e-9);
+        }
+
+        // The ranges are recomputed as values are added after the normalization.
+        let mut late = SearchEngine::new()
+            .with_values(articles().into_iter().take(1).collect())
+            .with_field_normalization(weighted(1., 1.), CombineMode::Max, metric, &queries);
+        for article in articles().into_iter().skip(1) {
+            late.add_value(article);
+        }
+        let late_results = late.similarities("dublin");
+        for ((late, late_score), (article, score)) in late_results.iter().zip(&results) {
+            assert_eq!(late.title, article.title);
+            assert_eq!(late_score, score);
+        }
+
+        // Without a range, e.g. for a single value and query, the scores are used as is.
+        let single = SearchEngine::new()
+            .with_values(articles().into_iter().take(1).collect())
+            .with_field_normalization(weighted(1., 1.), CombineMode::Max, metric, &["dublin"]);
+        let raw_single = SearchEngine::new()
+            .with_values(articles().into_iter().take(1).collect())
+            .with_field_weights(&weights, fields(), metric);
+        assert_eq!(
+            single.similarities("dublin")[0].1,
+            raw_single.similarities("dublin")[0].1
+        );
+        assert!(single.similarities("dublin")[0].1 > 0.);
+    }
+
+    #[test]
+    fn test_similar_to() {
+        let values = vec!["colour", "banana", "color", "colours", "apple"];
+
+        let engine = SearchEngine::new()
+            .with_values(values.clone())
+            .with_searchable();
+        let results = engine.similar_to(0, 2);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(value, _)| **value != "colour"));
+        assert_eq!(results[0].0, &"colours");
+        assert_eq!(results[1].0, &"color");
+
+        let mut stateful = SearchEngine::new().with_values(values).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.similarity(q),
+        );
+        let results = stateful.similar_to(2, 4);
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|(value, _)| **value != "color"));
+        assert_eq!(results[0], (&"colour", 5. / 6.));
+
+        // The skipped state of the reference value still catches up with the next query.
+        assert_eq!(stateful.best_match("color"), Some((&"color", 1.)));
+    }
+
+    #[test]
+    fn test_length_normalization() {
+        let engine = SearchEngine::new()
+            .with_values(vec!["category", "cat", "catalog"])
+            .with(|v: &&str, q: &str| partial_ratio(v, q));
+
+        // Both contain the query, so the tie keeps the insertion order.
+        assert_eq!(engine.best_match("cat"), Some((&"category", 1.)));
+
+        let mut normalized = engine.with_length_normalization(0.5);
+        assert_eq!(normalized.best_match("cat"), Some((&"cat", 1. + 0.5 / 3.)));
+        let results = normalized.similarities_descending("cat");
+        assert_eq!(results[1], (&"catalog", 1. + 0.5 / 7.));
+        assert_eq!(results[2], (&"category", 1. + 0.5 / 8.));
+
+        // A clearly better match still beats a shorter value.
+        assert_eq!(normalized.best_match("catalog").unwrap().0, &"catalog");
+    }
+
+    #[test]
+    fn test_top_n() {
+        let values: Vec<String> = (0..200)
+            .map(|i| format!("{}{}", VALUES[i % VALUES.len()], i % 7))
+            .collect();
+
+        let engine = SearchEngine::new()
+            .with_values(values.clone())
+            .with(|v: &String, q: &str| weighted_levenshtein_similarity(q, v));
+        let mut stateful = SearchEngine::new().with_values(values).with_state(
+            |v: &String| IncrementalLevenshtein::new("", v),
+            |s, _, q: &str| s.weighted_similarity(q),
+        );
+
+        for query in ["hel", "help3", "wrld", "x"] {
+            let expected = engine.similarities_top_k(query, 5);
+            let (slots, filled) = engine.top_n::<5>(query);
+            assert_eq!(filled, 5);
+            assert_eq!(slots.map(Option::unwrap).to_vec(), expected);
+
+            let (slots, filled) = stateful.top_n::<5>(query);
+            assert_eq!(filled, 5);
+            assert_eq!(slots.map(Option::unwrap).to_vec(), expected);
+        }
+
+        let small = SearchEngine::new()
+            .with_values(VALUES[..3].to_vec())
+            .with(|v, q| weighted_levenshtein_similarity(q, v));
+        let (slots, filled) = small.top_n::<5>("hello");
+        assert_eq!(filled, 3);
+        assert_eq!(slots[0], Some((&"hello", 1.)));
+        assert_eq!(slots[3..], [None, None]);
+    }
+
+    #[test]
+    fn test_precomputed() {
+        let precomputed = Cell::new(0);
+        let mut engine = SearchEngine::new()
+            .with_values(vec!["the quick fox", "a lazy dog", "quick dog"])
+            .with_precomputed(
+                |v: &&str| {
+                    precomputed.set(precomputed.get() + 1);
+                    v.split(' ').map(str::to_string).collect::<Vec<_>>()
+                },
+                |tokens, q: &str| {
+                    let matches = q.split(' ').filter(|t| tokens.iter().any(|v| v == t));
+                    matches.count() as f64 / tokens.len() as f64
+                },
+            );
+        assert_eq!(precomputed.get(), 3);
+
+        for query in ["quick", "dog", "quick dog", "fox"] {
+            engine.similarities(query);
+        }
+        assert_eq!(engine.best_match("quick dog"), Some((&"quick dog", 1.)));
+        assert_eq!(precomputed.get(), 3);
+
+        engine.add_value("lazy fox");
+        assert_eq!(precomputed.get(), 4);
+        engine.similarities("fox");
+        assert_eq!(precomputed.get(), 4);
+    }
+
+    #[test]
+    fn test_similarities_above_edge_cases() {
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v, q| normalized_levenshtein_similarity(q, v));
+        assert_eq!(
+            engine.similarities_above("hallo", 0.),
+            engine.similarities("hallo")
+        );
+
+        // A NaN score is kept through the combination, so even a threshold of 0.0 excludes it.
+        let engine = SearchEngine::new()
+            .with_values(VALUES.to_vec())
+            .with(|v: &&str, q: &str| match *v {
+                "foo" => f64::NAN,
+                _ => normalized_levenshtein_similarity(q, v),
+            });
+        let results = engine.similarities("hallo");
+        assert!(results.iter().any(|(v, s)| **v == "foo" && s.is_nan()));
+        let above = engine.similarities_above("hallo", 0.);
+        assert_eq!(above.len(), VALUES.len() - 1);
+        assert!(above.iter().all(|(v, s)| **v != "foo" && *s >= 0.));
+    }
+
+    #[test]
+    fn test_remove_retain_clear() {
+        let mut engine = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+        assert_eq!(engine.search("hallo")[0], &"hello");
+
+        assert_eq!(engine.remove_value(0), Some("hello"));
+        assert_eq!(engine.remove_value(VALUES.len()), None);
+        assert!(!engine.search("hallo").contains(&&"hello"));
+        assert_eq!(engine.search("hallo")[0], &"hollow");
+
+        engine.retain(|v| !v.starts_with('h'));
+        let mut remaining = engine.search("hallo");
+        remaining.sort();
+        assert_eq!(remaining, vec![&"bar", &"foo", &"world"]);
+
+        // The states stay aligned with their values.
+        for (state, value) in engine.get_values_with_state() {
+            assert_eq!(state.0.data(), *value);
+        }
+
+        engine.clear();
+        assert!(engine.search("hallo").is_empty());
+        engine.add_value("hello");
+        assert_eq!(engine.search("hallo"), vec![&"hello"]);
+    }
+
+    #[test]
+    fn test_len_and_values() {
+        let mut engine = SearchEngine::new().with_values(VALUES.to_vec()).with_state(
+            |v| IncrementalLevenshtein::new("", v),
+            |s, _, q| s.weighted_similarity(q),
+        );
+        assert_eq!(engine.len(), VALUES.len());
+        assert!(!engine.is_empty());
+        assert_eq!(engine.values().copied().collect::<Vec<_>>(), VALUES);
+
+        engine.clear();
+        assert_eq!(engine.len(), 0);
+        assert!(engine.is_empty());
+        assert_eq!(engine.values().next(), None);
+
+        let engine = SearchEngine::<&str, str, _, _>::new();
+        assert!(engine.is_empty());
+    }
+
+    #[test]
+    fn test_combine_modes() {
+        let values = vec![(0.2, 0.8), (0.6, 0.6)];
+        let expected = [
+            (CombineMode::Max, [0.8, 0.6]),
+            (CombineMode::Sum, [1., 1.2]),
+            (CombineMode::Mean, [0.5, 0.6]),
+            (CombineMode::Min, [0.2, 0.6]),
+            (CombineMode::Product, [0.2 * 0.8, 0.6 * 0.6]),
+        ];
+
+        for (mode, [first, second]) in expected {
+            let engine = SearchEngine::new()
+                .with_values(values.clone())
+                .with(|v: &(f64, f64), _: &str| v.0)
+                .with_weight_using(1., mode, |v, _| v.1);
+
+            let mut similarities = engine.similarities("query");
+            similarities.sort_by(|a, b| a.0.partial_cmp(b.0).unwrap());
+            assert_eq!(
+                similarities,
+                vec![(&values[0], first), (&values[1], second)]
+            );
+
+            let explanation = engine.explain("query");
+            for (value, total, _) in explanation {
+                let similarity = if *value == values[0] { first } else { second };
+                assert_eq!(total, similarity);
+            }
+        }
+
+        // Every layer is folded into the previous ones, so a mean of three layers stays a mean.
+        let mut engine = SearchEngine::new()
+            .with_values(values.clone())
+            .with(|v: &(f64, f64), _: &str| v.0)
+            .with_weight_using(1., CombineMode::Mean, |v, _| v.1)
+            .with_state_using(1., CombineMode::Mean, |_| (), |_, _, _| 1.);
+        let similarities = engine.similarities("query");
+        assert_eq!(similarities[0].0, &values[1]);
+        assert!((similarities[0].1 - 2.2 / 3.).abs() < 1e-12);
+        assert!((similarities[1].1 - 2. / 3.).abs() < 1e-12);
+
+        // The mean only sees the combined similarity of the previous layers, here their maximum.
+        let engine = SearchEngine::new()
+            .with_values(vec![(0.2, 0.8)])
+            .with(|v: &(f64, f64), _: &str| v.0)
+            .with(|v, _| v.1)
+            .with_weight_using(1., CombineMode::Mean, |_, _| 1.);
+        let similarity = engine.similarities("query")[0].1;
+        assert!((similarity - (0.8 * 2. + 1.) / 3.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_explain_total_with_wrappers() {
+        let modes = [
+            CombineMode::Max,
+            CombineMode::Sum,
+            CombineMode::Mean,
+            CombineMode::Min,
+            CombineMode::Product,
+        ];
+        for mode in modes {
+            let engine = SearchEngine::new()
+                .with_values(VALUES.to_vec())
+                .with_weight_using(1., mode, |v: &&str, q: &str| {
+                    normalized_levenshtein_similarity(q, v)
+                })
+                .with_weight_using(0.5, mode, |v, q| partial_ratio(q, v))
+                .with_weight_using(2., mode, |v, _| 1. / v.len() as f64);
+
+            let mut normalized = engine.clone().with_length_normalization(2.);
+            for query in ["hallo", "wor"] {
+                let similarities: HashMap<_, _> = normalized
+                    .similarities(query)
+                    .into_iter()
+                    .map(|(v, s)| (*v, s))
+                    .collect();
+                for (value, total, _) in normalized.explain(query) {
+                    assert_eq!(total, similarities[value], "{mode:?} {value}");
+                }
+            }
+
+            let empty = engine.with_empty_query_score(0.5);
+            for query in ["", "hallo"] {
+                let similarities: HashMap<_, _> = empty
+                    .similarities(query)
+                    .into_iter()
+                    .map(|(v, s)| (*v, s))
+                    .collect();
+                for (value, total, _) in empty.explain(query) {
+                    assert_eq!(total, similarities[value], "{mode:?} {value}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(all(test, feature = "serde", feature = "serde_json"))]
+mod tests {
+    use simple_search::levenshtein::incremental::IncrementalLevenshtein;
+
+    #[test]
+    fn test_incremental_round_trip() {
+        let mut incremental = IncrementalLevenshtein::new_case_insensitive("", "Hello World");
+        incremental.weighted_similarity("hel");
+
+        let json = serde_json::to_string(&incremental).unwrap();
+        let mut restored: IncrementalLevenshtein = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.query(), "hel");
+        assert_eq!(restored.data(), "hello world");
+        assert_eq!(restored.matrix(), incremental.matrix());
+
+        for query in ["hell", "HELLO W", "world", ""] {
+            let mut fresh = IncrementalLevenshtein::new_case_insensitive("", "Hello World");
+            assert_eq!(
+                restored.weighted_similarity(query),
+                fresh.weighted_similarity(query)
+            );
+            assert_eq!(restored.similarity(query), fresh.similarity(query));
+        }
+    }
+
+    #[test]
+    fn test_incremental_round_trip_skipped_rows() {
+        // The rows of the common suffix "lo" aren't computed, so they mustn't be reused after restoring.
+        let mut incremental = IncrementalLevenshtein::new("", "hello");
+        incremental.similarity("yello");
+
+        let json = serde_json::to_string(&incremental).unwrap();
+        let mut restored: IncrementalLevenshtein = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.recomputed_cells(), 0);
+        for query in ["yellow", "yell", "hello", ""] {
+            let mut fresh = IncrementalLevenshtein::new("", "hello");
+            assert_eq!(
+                restored.weighted_similarity(query),
+                fresh.weighted_similarity(query)
+            );
+            assert_eq!(restored.similarity(query), fresh.similarity(query));
+        }
+    }
+
+    #[test]
+    fn test_incremental_inconsistent_snapshot() {
+        let incremental = IncrementalLevenshtein::new("ab", "abc");
+        let mut snapshot: serde_json::Value = serde_json::to_value(&incremental).unwrap();
+        assert!(snapshot.get("query_chars").is_none());
+        assert!(snapshot.get("valid_rows").is_none());
+
+        // A row is missing.
+        snapshot["matrix"].as_array_mut().unwrap().pop();
+        assert!(serde_json::from_value::<IncrementalLevenshtein>(snapshot.clone()).is_err());
+
+        // A row is too short.
+        let mut snapshot: serde_json::Value = serde_json::to_value(&incremental).unwrap();
+        snapshot["matrix"][1].as_array_mut().unwrap().pop();
+        assert!(serde_json::from_value::<IncrementalLevenshtein>(snapshot).is_err());
+
+        // The query doesn't match the matrix.
+        let mut snapshot: serde_json::Value = serde_json::to_value(&incremental).unwrap();
+        snapshot["query"] = "abcd".into();
+        assert!(serde_json::from_value::<IncrementalLevenshtein>(snapshot).is_err());
+    }
+}
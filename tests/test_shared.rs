@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::levenshtein::incremental::IncrementalLevenshtein;
+    use simple_search::search_engine::SearchEngine;
+    use simple_search::shared::SharedState;
+
+    type State = (SharedState<IncrementalLevenshtein>, ());
+
+    fn shared(engine: &[(State, &str)], clone: &[(State, &str)]) -> Vec<bool> {
+        engine
+            .iter()
+            .zip(clone)
+            .map(|((original, _), (cloned, _))| SharedState::ptr_eq(&original.0, &cloned.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_shared_state() {
+        let engine = SearchEngine::new()
+            .with_values(vec!["hello", "world", "help"])
+            .with_shared_state(
+                |v| IncrementalLevenshtein::new("", v),
+                |s, v, q: &str| {
+                    if v.starts_with(&q[..1]) {
+                        s.get_mut().weighted_similarity(q)
+                    } else {
+                        0.
+                    }
+                },
+            );
+
+        let mut clone = engine.clone();
+
+        // Cloning only copies the pointers.
+        assert_eq!(
+            shared(
+                engine.get_values_with_state(),
+                clone.get_values_with_state()
+            ),
+            vec![true; 3]
+        );
+
+        let results = clone.search("hallo");
+        assert_eq!(results[0], &"hello");
+
+        // Only the states of the values starting with "h" were copied.
+        assert_eq!(
+            shared(
+                engine.get_values_with_state(),
+                clone.get_values_with_state()
+            ),
+            vec![false, true, false]
+        );
+
+        assert_eq!(engine.get_values_with_state()[0].0 .0.matrix_cells(), 6);
+        assert_eq!(clone.get_values_with_state()[0].0 .0.matrix_cells(), 6 * 6);
+    }
+
+    #[test]
+    fn test_values_ref_counted() {
+        let engine = SearchEngine::new()
+            .with_values(vec!["hello", "world", "help"])
+            .with_values_ref_counted(
+                |v| IncrementalLevenshtein::new("", v),
+                |s, _, q: &str| s.weighted_similarity(q),
+            );
+
+        let mut clone = engine.clone();
+        assert_eq!(
+            shared(
+                engine.get_values_with_state(),
+                clone.get_values_with_state()
+            ),
+            vec![true; 3]
+        );
+
+        assert_eq!(clone.search("hallo")[0], &"hello");
+
+        // Every scored state was copied, the original engine is unchanged.
+        assert_eq!(
+            shared(
+                engine.get_values_with_state(),
+                clone.get_values_with_state()
+            ),
+            vec![false; 3]
+        );
+        assert_eq!(engine.get_values_with_state()[0].0 .0.matrix_cells(), 6);
+    }
+}
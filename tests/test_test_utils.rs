@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::test_utils::assert_rankings_equivalent;
+
+    #[test]
+    fn test_tie_reordering_is_equivalent() {
+        let a = [("a", 0.2), ("b", 0.5), ("c", 0.5), ("d", 0.9)];
+        let b = [("a", 0.2), ("c", 0.5), ("b", 0.5 + 1e-12), ("d", 0.9)];
+        assert_rankings_equivalent(&a, &b, 1e-9);
+        assert_rankings_equivalent::<&str>(&[], &[], 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_different_order_is_rejected() {
+        let a = [("a", 0.2), ("b", 0.5)];
+        let b = [("b", 0.2), ("a", 0.5)];
+        assert_rankings_equivalent(&a, &b, 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_different_scores_are_rejected() {
+        let a = [("a", 0.2), ("b", 0.5), ("c", 0.5)];
+        let b = [("a", 0.2), ("b", 0.5), ("c", 0.6)];
+        assert_rankings_equivalent(&a, &b, 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_different_values_are_rejected() {
+        let a = [("a", 0.2), ("b", 0.5)];
+        let b = [("a", 0.2), ("x", 0.5)];
+        assert_rankings_equivalent(&a, &b, 1e-9);
+    }
+}
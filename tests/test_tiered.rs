@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::levenshtein::base::weighted_levenshtein_similarity;
+    use simple_search::search_engine::SearchEngine;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_tiered_skips_losing_candidates() {
+        let values = vec![
+            "apple",
+            "apply",
+            "ample",
+            "zebra",
+            "xylophone",
+            "quartz",
+            "banana",
+        ];
+        let cheap = |v: &&str, q: &str| if v.starts_with(&q[..1]) { 0.1 } else { 0.0 };
+        let expensive = |v: &&str, q: &str| weighted_levenshtein_similarity(q, v);
+
+        let full = SearchEngine::new()
+            .with_values(values.clone())
+            .with(cheap)
+            .with(expensive);
+        let expected = full.similarities_top_k("appel", 2);
+
+        let calls = Cell::new(0);
+        let tiered = SearchEngine::new().with_values(values.clone()).with_tiered(
+            cheap,
+            |v: &&str, q: &str| {
+                calls.set(calls.get() + 1);
+                expensive(v, q)
+            },
+            0.05,
+        );
+        let results = tiered.similarities_top_k("appel", 2);
+
+        assert_eq!(results, expected);
+        assert_eq!(calls.get(), 3);
+        assert_eq!(tiered.similarities("appel", 2).len(), values.len());
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use simple_search::levenshtein::base::weighted_levenshtein_similarity;
+    use simple_search::levenshtein::incremental::IncrementalLevenshtein;
+    use simple_search::search_engine::SearchEngine;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Counts the events emitted while it is the default subscriber.
+    struct EventCounter(Arc<AtomicUsize>);
+
+    impl Subscriber for EventCounter {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn test_event_per_search() {
+        let events = Arc::new(AtomicUsize::new(0));
+        let subscriber = EventCounter(events.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let engine = SearchEngine::new()
+                .with_values(vec!["hello", "world"])
+                .with(|v, q| weighted_levenshtein_similarity(q, v));
+            engine.similarities("hello");
+            engine.search("world");
+            engine.similarities("");
+            assert_eq!(events.load(Ordering::SeqCst), 3);
+
+            let mut engine = SearchEngine::new()
+                .with_values(vec!["hello", "world"])
+                .with_state(
+                    |v| IncrementalLevenshtein::new("", v),
+                    |s, _, q| s.weighted_similarity(q),
+                );
+            engine.similarities("hel");
+            engine.search("hell");
+            assert_eq!(events.load(Ordering::SeqCst), 5);
+        });
+    }
+
+    #[test]
+    fn test_event_per_entry_point() {
+        let events = Arc::new(AtomicUsize::new(0));
+        let subscriber = EventCounter(events.clone());
+        let count = || events.swap(0, Ordering::SeqCst);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let engine = SearchEngine::new()
+                .with_values(vec!["hello", "world", "help"])
+                .with(|v, q| weighted_levenshtein_similarity(q, v));
+            engine.best_match("hel");
+            engine.best_match_above("hel", 0.5);
+            engine.top_n::<2>("hel");
+            engine.similarities_top_k("hel", 2);
+            engine.search_descending("hel");
+            engine.similarities_above("hel", 0.5);
+            engine.count_above("hel", 0.5);
+            engine.scores("hel");
+            engine.grouped_search("hel", &[0.5]);
+            engine.match_percentages("hel");
+            engine.clone().into_similarities("hel");
+            engine.clone().into_search("hel");
+            engine.clone().into_best_match("hel");
+            assert_eq!(count(), 13);
+
+            #[cfg(feature = "rayon")]
+            {
+                engine.par_similarities("hel");
+                engine.par_search("hel");
+                engine.par_scores("hel");
+                engine.par_count_above("hel", 0.5);
+                engine.clone().into_par_similarities("hel");
+                engine.clone().into_par_top_k("hel", 2);
+                assert_eq!(count(), 6);
+            }
+
+            let mut engine = SearchEngine::new()
+                .with_values(vec!["hello", "world", "help"])
+                .with_state(
+                    |v| IncrementalLevenshtein::new("", v),
+                    |s, _, q| s.weighted_similarity(q),
+                );
+            engine.best_match("hel");
+            engine.top_n::<2>("hel");
+            engine.search_top_k("hel", 2);
+            engine.similarities_ascending("hel");
+            engine.count_above("hel", 0.5);
+            engine.approx_search("hel", 2, 1, 7);
+            assert_eq!(count(), 6);
+        });
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use simple_search::levenshtein::base::weighted_levenshtein_similarity;
+    use simple_search::levenshtein::incremental::IncrementalLevenshtein;
+    use simple_search::search_engine::SearchEngine;
+    use simple_search::type_erasure::cloneable;
+    use simple_search::type_erasure::non_cloneable::{
+        ImmutableSearchEngine, ImmutableSearchEngineRef, MutableSearchEngineRef,
+    };
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_shared_immutable_engine() {
+        let engine: ImmutableSearchEngine<String, str> = SearchEngine::new()
+            .with_values(vec!["hello".to_string(), "world".to_string()])
+            .with(|v: &String, q: &str| weighted_levenshtein_similarity(q, v))
+            .erase_type();
+        let engine = Arc::new(engine);
+
+        let handles: Vec<_> = ["hallo", "wrld", "hell", "word"]
+            .into_iter()
+            .map(|query| {
+                let engine = Arc::clone(&engine);
+                thread::spawn(move || engine.search(query).len())
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 2);
+        }
+
+        let engine: cloneable::ImmutableSearchEngine<&str, str> = SearchEngine::new()
+            .with_values(vec!["hello", "world"])
+            .with(|v: &&str, q: &str| weighted_levenshtein_similarity(q, v))
+            .erase_type_cloneable();
+        let engine = Arc::new(engine);
+
+        thread::scope(|scope| {
+            for query in ["hallo", "wrld"] {
+                let engine = Arc::clone(&engine);
+                scope.spawn(move || assert_eq!(engine.search(query).len(), 2));
+            }
+        });
+    }
+
+    #[test]
+    fn test_erase_borrowed_engine() {
+        let text = String::from("hello world foo bar");
+        let words: Vec<&str> = text.split(' ').collect();
+
+        let engine: ImmutableSearchEngineRef<&str, str> = SearchEngine::new()
+            .with_values(words.clone())
+            .with(|v: &&str, q: &str| weighted_levenshtein_similarity(q, v))
+            .erase_type_ref();
+        assert_eq!(engine.search("hallo").first(), Some(&&"hello"));
+
+        let mut engine: MutableSearchEngineRef<&str, str> = SearchEngine::new()
+            .with_values(words)
+            .with_state(
+                |v: &&str| IncrementalLevenshtein::new("", v),
+                |s, _, q: &str| s.weighted_similarity(q),
+            )
+            .erase_type_ref();
+        assert_eq!(engine.search("wrld").first(), Some(&&"world"));
+    }
+
+    #[test]
+    fn test_erased_ordering_options() {
+        let values = vec!["hello", "world", "foo", "bar", "help", "hollow", "yellow"];
+
+        let engine = SearchEngine::new()
+            .with_values(values.clone())
+            .with(|v: &&str, q: &str| weighted_levenshtein_similarity(q, v));
+        let erased = engine.clone().erase_type_cloneable();
+
+        for query in ["hallo", "wrld"] {
+            assert_eq!(
+                engine.similarities_descending(query),
+                erased.similarities_descending(query)
+            );
+            assert_eq!(
+                engine.search_descending(query),
+                erased.search_descending(query)
+            );
+            assert_eq!(
+                engine.similarities_top_k(query, 3),
+                erased.similarities_top_k(query, 3)
+            );
+            assert_eq!(engine.search_top_k(query, 3), erased.search_top_k(query, 3));
+            assert_eq!(
+                engine.similarities_above(query, 0.2),
+                erased.similarities_above(query, 0.2)
+            );
+            assert_eq!(
+                engine.search_above(query, 0.2),
+                erased.search_above(query, 0.2)
+            );
+        }
+
+        let new_engine = || {
+            SearchEngine::new().with_values(values.clone()).with_state(
+                |v: &&str| IncrementalLevenshtein::new("", v),
+                |s, _, q: &str| s.weighted_similarity(q),
+            )
+        };
+        let mut engine = new_engine();
+        let mut erased = new_engine().erase_type();
+
+        for query in ["h", "he", "hel", "hell"] {
+            assert_eq!(
+                engine.similarities_descending(query),
+                erased.similarities_descending(query)
+            );
+            assert_eq!(
+                engine.similarities_top_k(query, 2),
+                erased.similarities_top_k(query, 2)
+            );
+            assert_eq!(
+                engine.similarities_above(query, 0.3),
+                erased.similarities_above(query, 0.3)
+            );
+        }
+    }
+}